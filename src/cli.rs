@@ -7,6 +7,7 @@ use crate::impls::weather::WeatherHandler;
 use crate::utils::consts::BANNER;
 use clap::{Parser, Subcommand};
 use crossterm::style::Stylize;
+use std::process::ExitCode;
 
 #[derive(Debug, Parser)]
 #[command(name = "fun", author, version, about, long_about = BANNER )]
@@ -25,7 +26,7 @@ pub enum Commands {
     Music(MusicHandler),
 
     #[command(name = "curl", version, about = "curl系统")]
-    Curl(CurlHandler),
+    Curl(Box<CurlHandler>),
 
     #[command(name = "game", version, about = "游戏系统")]
     Game(GameHandler),
@@ -35,17 +36,14 @@ pub enum Commands {
 }
 
 impl Commands {
-    /// 执行子命令
-    pub fn run(self) {
+    /// 执行子命令，出错时返回非0退出码，便于在脚本和CI中使用
+    pub fn run(self) -> ExitCode {
         let combine_handlers = CombineHandler::new();
-        match combine_handlers.matches_handler(self) {
-            Ok(handler) => {
-                if let Err(cli_err) = handler.run() {
-                    eprintln!("{}: {}", "error".red().bold(), cli_err.to_string().italic());
-                }
-            }
+        match combine_handlers.matches_handler(self).and_then(|handler| handler.run()) {
+            Ok(()) => ExitCode::SUCCESS,
             Err(cli_err) => {
                 eprintln!("{}: {}", "error".red().bold(), cli_err.to_string().italic());
+                ExitCode::FAILURE
             }
         }
     }
@@ -8,6 +8,7 @@ pub enum CliError {
     FileSysError(String),
     NetRequestError(String),
     UnknownError(String),
+    AssertionError(String),
 }
 
 impl std::fmt::Display for CliError {
@@ -18,6 +19,7 @@ impl std::fmt::Display for CliError {
             CliError::FileSysError(err) => write!(f, "file sys error:{}", err),
             CliError::NetRequestError(err) => write!(f, "net request error:{}", err),
             CliError::UnknownError(err) => write!(f, "unknown error! {}", err),
+            CliError::AssertionError(err) => write!(f, "assertion failed: {}", err),
         }
     }
 }
@@ -34,7 +36,14 @@ impl From<std::io::Error> for CliError {
 }
 impl From<reqwest::Error> for CliError {
     fn from(e: reqwest::Error) -> Self {
-        CliError::NetRequestError(e.to_string())
+        // reqwest的错误信息较笼统，拼接底层原因便于排查（如证书、重定向、超时等）
+        let mut message = e.to_string();
+        let mut source = e.source();
+        while let Some(cause) = source {
+            message.push_str(&format!(": {}", cause));
+            source = cause.source();
+        }
+        CliError::NetRequestError(message)
     }
 }
 impl From<rodio::StreamError> for CliError {
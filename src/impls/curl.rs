@@ -1,28 +1,220 @@
 use crate::error::CliError;
 use crate::impls::handlers::CommandHandler;
-use clap::{Parser, ValueEnum, ValueHint};
-use reqwest::blocking::Client;
-use std::path::PathBuf;
-use std::time::Instant;
+use crate::impls::http::assertion::{self, Assertion};
+use crate::impls::http::bench::BenchHandler;
+use crate::impls::http::body::{self, BodyPrinter};
+use crate::impls::http::client::{ClientArgs, RedirectChain};
+use crate::impls::http::collection::RunHandler;
+use crate::impls::http::cookie::CookieJar;
+use crate::impls::http::curl_command::{CurlCommandLine, shell_quote};
+use crate::impls::http::download::Downloader;
+use crate::impls::http::history::{self, HistoryHandler, ReplayHandler};
+use crate::impls::http::mock::MockHandler;
+use crate::impls::http::session::Session;
+use crate::impls::http::sse;
+use crate::impls::http::tui::TuiClient;
+use crate::impls::http::upload::{Payload, RequestBody};
+use crate::impls::http::websocket;
+use crate::ui::theme::Theme;
+use clap::{Args, Parser, Subcommand, ValueHint};
+use reqwest::blocking::{Client, Request, RequestBuilder};
+use reqwest::header::{ACCEPT_ENCODING, CONTENT_ENCODING, CONTENT_TYPE, HeaderValue};
+use reqwest::{Method, Version};
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use url::Url;
 
 #[derive(Debug, Parser)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 pub struct CurlHandler {
+    #[command(subcommand)]
+    command: Option<CurlCommand>,
+
+    #[command(flatten)]
+    request: Option<RequestArgs>,
+
+    #[command(flatten)]
+    client: ClientArgs,
+
+    #[arg(
+        short,
+        long,
+        value_hint = ValueHint::FilePath,
+        help = "保存响应的输出文件"
+    )]
+    output: Option<PathBuf>,
+
+    #[arg(
+        short = 'C',
+        long = "continue",
+        requires = "output",
+        help = "断点续传，从输出文件已有的大小处继续下载"
+    )]
+    resume: bool,
+
+    #[arg(long, requires = "output", help = "下载完成后校验文件的SHA256摘要")]
+    sha256: Option<String>,
+
+    #[arg(
+        short = 'b',
+        long,
+        value_name = "FILE|NAME=VALUE",
+        help = "发送Cookie，可以是Netscape格式的Cookie文件，也可以是name=value"
+    )]
+    cookie: Vec<String>,
+
+    #[arg(long, value_hint = ValueHint::FilePath, help = "从该文件读取Cookie，并在请求结束后以Netscape格式写回")]
+    cookie_jar: Option<PathBuf>,
+
+    #[arg(long, value_name = "NAME", help = "命名会话，自动复用Cookie和默认请求头")]
+    session: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "HEADER",
+        requires = "session",
+        help = "保存到会话的请求头名称，可重复指定，如 --session-header Authorization"
+    )]
+    session_header: Vec<String>,
+
+    #[arg(
+        long,
+        value_name = "CURL_COMMAND",
+        conflicts_with = "url",
+        help = "从curl命令导入请求，支持-X、-H、-d、--data-raw、-u、-b、--compressed等常用参数"
+    )]
+    from_curl: Option<String>,
+
+    #[arg(long, help = "仅打印等价的curl命令，不发送请求")]
+    print_curl: bool,
+
+    #[arg(
+        short = 'I',
+        long,
+        conflicts_with_all = ["method", "output"],
+        help = "发送HEAD请求，只输出响应头"
+    )]
+    head: bool,
+
+    #[arg(short, long, help = "以curl的格式输出，包含状态行和响应头")]
+    include: bool,
+
+    #[arg(
+        short,
+        long,
+        conflicts_with = "output",
+        help = "输出发送的请求行和请求头（以>开头）、收到的状态行和响应头（以<开头）"
+    )]
+    verbose: bool,
+
+    #[arg(
+        long,
+        conflicts_with_all = ["output", "expect_json", "expect_body_contains"],
+        help = "SSE模式：持续打印服务端推送的事件；响应类型为text/event-stream时自动启用"
+    )]
+    sse: bool,
+
+    #[arg(long, value_name = "BYTES", help = "最多显示的响应体字节数，超出部分截断")]
+    max_body_display: Option<usize>,
+
+    #[arg(long, conflicts_with = "output", help = "以十六进制格式显示响应体")]
+    hexdump: bool,
+
+    #[arg(
+        long,
+        conflicts_with = "output",
+        help = "二进制响应体自动保存到当前目录，文件名取自URL"
+    )]
+    save_binary: bool,
+
+    #[arg(long, help = "不记录本次请求到请求历史")]
+    no_history: bool,
+
+    #[arg(
+        long,
+        conflicts_with_all = ["url", "from_curl", "output"],
+        help = "全屏交互式客户端，可编辑请求、查看响应和历史请求"
+    )]
+    tui: bool,
+
+    #[arg(long, value_enum, requires = "tui", default_value_t = Theme::Cyberpunk, help = "交互式客户端的主题/cyberpunk/blackgold/fire/ocean/aurora")]
+    theme: Theme,
+
+    #[arg(
+        long,
+        value_name = "STATUS",
+        conflicts_with = "output",
+        value_parser = assertion::parse_expect_status,
+        help = "断言状态码，如200、2xx、200,201；指定后非2xx响应不再视为错误"
+    )]
+    expect_status: Option<Assertion>,
+
+    #[arg(
+        long,
+        value_name = "NAME[:VALUE]",
+        conflicts_with = "output",
+        value_parser = assertion::parse_expect_header,
+        help = "断言响应头存在，或其值等于VALUE"
+    )]
+    expect_header: Vec<Assertion>,
+
+    #[arg(
+        long,
+        value_name = "EXPRESSION",
+        conflicts_with = "output",
+        value_parser = assertion::parse_expect_json,
+        help = "断言JSON响应，如'.data.id exists'、'.data.count >= 1'、'.code == 0'"
+    )]
+    expect_json: Vec<Assertion>,
+
+    #[arg(
+        long,
+        value_name = "TEXT",
+        conflicts_with = "output",
+        help = "断言响应体包含指定文本"
+    )]
+    expect_body_contains: Vec<String>,
+}
+
+#[derive(Debug, Subcommand)]
+enum CurlCommand {
+    #[command(name = "bench", about = "压测模式，并发请求并实时展示统计信息")]
+    Bench(BenchHandler),
+
+    #[command(name = "run", about = "执行请求集合中的请求")]
+    Run(RunHandler),
+
+    #[command(name = "history", about = "查看和搜索请求历史")]
+    History(HistoryHandler),
+
+    #[command(name = "replay", about = "重放请求历史中的请求，可覆盖请求方式、URL、请求头和请求体")]
+    Replay(ReplayHandler),
+
+    #[command(name = "mock", about = "启动本地模拟HTTP服务，提供静态目录或JSON/TOML中定义的路由")]
+    Mock(MockHandler),
+}
+
+// 请求参数：URL、请求方式、请求头和请求体，curl的各个子命令共用同一套请求模型
+#[derive(Debug, Clone, Args)]
+pub struct RequestArgs {
     #[arg(
         value_hint = ValueHint::Url,
         value_parser = parse_url,
         required = true,
-        help = "请求的URL"
+        help = "请求的URL，ws://、wss://地址进入WebSocket交互模式"
     )]
-    url: String,
+    pub url: String,
 
     #[arg(
         short='X',
         long,
-        value_enum,
+        value_parser = parse_method,
         default_value_t = HttpMethod::Get,
-        help = "HTTP请求方式"
+        help = "HTTP请求方式，支持任意方法名，如HEAD、TRACE、PROPFIND、PURGE"
     )]
-    method: HttpMethod,
+    pub method: HttpMethod,
 
     #[arg(
         short='H',
@@ -30,93 +222,602 @@ pub struct CurlHandler {
         help = "请求的Header",
         value_parser = parse_header
     )]
-    headers: Option<Vec<(String, String)>>,
-
-    #[arg(short, long, help = "请求的Body")]
-    data: Option<String>,
+    pub headers: Option<Vec<(String, String)>>,
 
     #[arg(
         short,
         long,
-        value_hint = ValueHint::FilePath,
-        help = "保存响应的输出文件"
+        help = "请求的Body，@file从文件读取，@-从标准输入读取，读取时去掉换行符"
     )]
-    output: Option<PathBuf>,
+    pub data: Option<String>,
+
+    #[arg(
+        long,
+        conflicts_with = "data",
+        help = "原样发送的请求体，支持@file和@-，大文件以流的方式上传"
+    )]
+    pub data_binary: Option<String>,
+
+    #[arg(
+        long,
+        conflicts_with_all = ["data", "data_binary"],
+        help = "请求体，与-d不同，@开头的内容不会当作文件读取"
+    )]
+    pub data_raw: Option<String>,
+
+    // 命令行中@file、@-读取后的请求体，由resolve_body填充
+    #[arg(skip)]
+    pub payload: Option<RequestBody>,
 }
 
-// 枚举类型：HTTP 方法
-#[derive(Debug, Clone, ValueEnum)]
-enum HttpMethod {
+// 枚举类型：HTTP 方法，非标准的方法（如WebDAV的PROPFIND、缓存的PURGE）使用Custom
+#[derive(Debug, Clone, PartialEq)]
+pub enum HttpMethod {
     Get,
     Post,
     Put,
     Patch,
     Delete,
     Options,
+    Head,
+    Trace,
+    Custom(String),
+}
+impl Display for HttpMethod {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let method = match self {
+            HttpMethod::Get => "GET",
+            HttpMethod::Post => "POST",
+            HttpMethod::Put => "PUT",
+            HttpMethod::Patch => "PATCH",
+            HttpMethod::Delete => "DELETE",
+            HttpMethod::Options => "OPTIONS",
+            HttpMethod::Head => "HEAD",
+            HttpMethod::Trace => "TRACE",
+            HttpMethod::Custom(method) => method,
+        };
+        write!(f, "{}", method)
+    }
 }
-fn parse_url(url: &str) -> Result<String, String> {
-    if url.starts_with("http://") || url.starts_with("https://") {
+
+/// 解析请求方式，不区分大小写，统一转为大写；方法名需符合RFC 9110中token的字符要求
+pub fn parse_method(method: &str) -> Result<HttpMethod, String> {
+    let method = method.trim().to_ascii_uppercase();
+    let is_token = !method.is_empty()
+        && method
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "!#$%&'*+-.^_`|~".contains(c));
+    if !is_token {
+        return Err(format!("Invalid method: {}", method));
+    }
+    Ok(match method.as_str() {
+        "GET" => HttpMethod::Get,
+        "POST" => HttpMethod::Post,
+        "PUT" => HttpMethod::Put,
+        "PATCH" => HttpMethod::Patch,
+        "DELETE" => HttpMethod::Delete,
+        "OPTIONS" => HttpMethod::Options,
+        "HEAD" => HttpMethod::Head,
+        "TRACE" => HttpMethod::Trace,
+        _ => HttpMethod::Custom(method),
+    })
+}
+
+pub fn parse_url(url: &str) -> Result<String, String> {
+    let schemes = ["http://", "https://", "ws://", "wss://"];
+    if schemes.iter().any(|scheme| url.starts_with(scheme)) {
         Ok(url.to_string())
     } else {
         Err(format!("Invalid URL: {}", url))
     }
 }
 
-fn parse_header(header: &str) -> Result<(String, String), String> {
-    let parts: Vec<&str> = header.split(":").collect();
-    if parts.len() != 2 {
-        return Err(format!("Invalid header format: {}", header));
+pub fn parse_header(header: &str) -> Result<(String, String), String> {
+    // 只按第一个冒号拆分，值中可能包含冒号（如URL、时间）
+    match header.split_once(':') {
+        Some((key, value)) if !key.trim().is_empty() => Ok((key.trim().to_string(), value.trim().to_string())),
+        _ => Err(format!("Invalid header format: {}", header)),
     }
-    Ok((parts[0].to_string(), parts[1].to_string()))
 }
 
-impl CommandHandler for CurlHandler {
-    fn run(&self) -> Result<(), CliError> {
-        let client = Client::new();
-        println!("🌍：请求URL: {}", self.url);
-        let mut req = match self.method {
-            HttpMethod::Get => client.get(&self.url),
-            HttpMethod::Post => client.post(&self.url),
-            HttpMethod::Put => client.put(&self.url),
-            HttpMethod::Patch => client.patch(&self.url),
-            HttpMethod::Delete => client.delete(&self.url),
-            HttpMethod::Options => client.request(reqwest::Method::OPTIONS, &self.url),
+impl RequestArgs {
+    /// 请求体的原始参数，以及是否需要原样发送
+    pub fn body(&self) -> Option<(&str, bool)> {
+        match (&self.data, &self.data_binary, &self.data_raw) {
+            (Some(data), _, _) | (None, None, Some(data)) => Some((data, false)),
+            (None, Some(data), _) => Some((data, true)),
+            (None, None, None) => None,
+        }
+    }
+
+    /// 读取命令行中`@file`、`@-`指定的请求体，未指定Content-Type时根据文件扩展名推断
+    ///
+    /// 只在解析命令行参数时调用一次，之后多次构建请求也不会重复读取文件或标准输入；
+    /// `stream`为true时大文件以流的方式上传
+    pub fn resolve_body(&mut self, stream: bool) -> Result<(), CliError> {
+        let (payload, binary) = match (&self.data, &self.data_binary) {
+            (Some(data), _) => (Payload::parse(data), false),
+            (None, Some(data)) => (Payload::parse(data), true),
+            (None, None) => return Ok(()),
         };
+        if matches!(payload, Payload::Text(_)) {
+            return Ok(());
+        }
+        let has_content_type = self
+            .headers
+            .iter()
+            .flatten()
+            .any(|(key, _)| key.eq_ignore_ascii_case("content-type"));
+        if let Some(content_type) = payload.content_type().filter(|_| !has_content_type) {
+            self.headers
+                .get_or_insert_with(Vec::new)
+                .push(("Content-Type".to_string(), content_type));
+        }
+        self.payload = Some(payload.read(binary, stream)?);
+        Ok(())
+    }
+
+    /// 根据请求参数构建请求，请求体按字面内容发送，`@file`需先通过resolve_body读取
+    pub fn build(&self, client: &Client) -> Result<RequestBuilder, CliError> {
+        // 方法名在解析时已校验为合法的token，转换不会失败
+        let method = Method::from_bytes(self.method.to_string().as_bytes()).unwrap_or(Method::GET);
+        let mut req = client.request(method, &self.url);
         if let Some(headers) = &self.headers {
-            println!("请求头：");
             for (key, value) in headers.iter() {
-                println!("{}:{}", key, value);
                 req = req.header(key, value);
             }
-            // headers.iter().for_each(|(key, value)| println!("{}:{}", key, value));
         }
-        if let Some(data) = &self.data {
-            req = req.body(data.to_string());
-            println!("请求体：{}", data);
+        match (&self.payload, self.body()) {
+            (Some(payload), _) => req = req.body(payload.to_body()?),
+            (None, Some((data, _))) => req = req.body(data.to_string()),
+            (None, None) => {}
+        }
+        Ok(req)
+    }
+}
+
+impl CurlHandler {
+    /// 加载会话、Cookie文件以及命令行中指定的Cookie
+    fn load_cookies(&self, url: &str, cookies: &[String], session: Option<&Session>) -> Result<CookieJar, CliError> {
+        let jar = CookieJar::default();
+        if let Some(session) = session {
+            jar.load(&session.cookie_path()?)?;
+        }
+        if let Some(path) = &self.cookie_jar {
+            jar.load(path)?;
+        }
+        let url = Url::parse(url).map_err(|e| CliError::UnknownError(format!("Invalid URL: {}", e)))?;
+        for cookie in cookies {
+            // 与curl一致：包含=的视为Cookie内容，否则视为Cookie文件
+            if cookie.contains('=') {
+                for pair in cookie.split(';').map(str::trim).filter(|pair| !pair.is_empty()) {
+                    jar.add_raw(pair, &url)?;
+                }
+            } else {
+                jar.load(Path::new(cookie))?;
+            }
+        }
+        Ok(jar)
+    }
+
+    /// 生成与本次调用等价的curl命令
+    fn to_curl_command(&self, request: &RequestArgs, client: &ClientArgs, cookies: &[String]) -> String {
+        let mut args = vec!["curl".to_string()];
+        let mut push = |flag: &str, value: Option<&str>| {
+            args.push(flag.to_string());
+            if let Some(value) = value {
+                args.push(shell_quote(value));
+            }
+        };
+        match request.method {
+            HttpMethod::Get => {}
+            // curl的-X HEAD会一直等待响应体，需使用-I
+            HttpMethod::Head => push("-I", None),
+            _ => push("-X", Some(&request.method.to_string())),
+        }
+        if self.include {
+            push("-i", None);
+        }
+        if self.verbose {
+            push("-v", None);
+        }
+        for (key, value) in request.headers.iter().flatten() {
+            push("-H", Some(&format!("{}: {}", key, value)));
+        }
+        match (request.body(), &request.data_raw) {
+            (Some((data, true)), _) => push("--data-binary", Some(data)),
+            // --data-raw不会把@开头的内容当作文件
+            (Some((data, false)), None) if data.starts_with('@') => push("-d", Some(data)),
+            (Some((data, false)), _) => push("--data-raw", Some(data)),
+            (None, _) => {}
+        }
+        for cookie in cookies {
+            push("-b", Some(cookie));
+        }
+        if let Some(path) = &self.cookie_jar {
+            push("-b", Some(&path.to_string_lossy()));
+            push("-c", Some(&path.to_string_lossy()));
+        }
+        if !client.no_follow {
+            push("-L", None);
+            push("--max-redirs", Some(&client.max_redirects.to_string()));
+        }
+        if let Some(secs) = client.connect_timeout {
+            push("--connect-timeout", Some(&secs.to_string()));
+        }
+        if let Some(secs) = client.timeout {
+            push("--max-time", Some(&secs.to_string()));
+        }
+        if let Some(proxy) = &client.proxy {
+            push("--proxy", Some(proxy));
+        }
+        if client.insecure {
+            push("-k", None);
+        }
+        for (flag, enabled) in [
+            ("--http1.1", client.http1_1),
+            ("--http2", client.http2),
+            ("--http2-prior-knowledge", client.http2_prior_knowledge),
+        ] {
+            if enabled {
+                push(flag, None);
+            }
+        }
+        for (flag, path) in [("--cacert", &client.cacert), ("--cert", &client.cert), ("--key", &client.key)] {
+            if let Some(path) = path {
+                push(flag, Some(&path.to_string_lossy()));
+            }
+        }
+        if let Some(output) = &self.output {
+            push("-o", Some(&output.to_string_lossy()));
+            if self.resume {
+                push("-C", Some("-"));
+            }
+        }
+        push("--compressed", None);
+        args.push(shell_quote(&request.url));
+        args.join(" ")
+    }
+
+    /// 命令行中指定的所有断言
+    fn assertions(&self) -> Vec<Assertion> {
+        self.expect_status
+            .iter()
+            .chain(self.expect_header.iter())
+            .chain(self.expect_json.iter())
+            .cloned()
+            .chain(self.expect_body_contains.iter().cloned().map(Assertion::BodyContains))
+            .collect()
+    }
+
+    fn record(&self, request: &RequestArgs, status: Option<u16>, error: Option<String>, elapsed: Duration) {
+        if !self.no_history {
+            history::record(request, status, error, elapsed);
+        }
+    }
+
+    /// 发送请求并输出响应
+    fn send(
+        &self,
+        request: &RequestArgs,
+        client: &Client,
+        client_args: &ClientArgs,
+        redirects: &RedirectChain,
+    ) -> Result<(), CliError> {
+        // -i、-I、--verbose使用curl的输出格式，只输出响应本身
+        let curl_style = self.include || self.head || self.verbose;
+        if !curl_style {
+            println!("🌍：请求URL: {}", request.url);
+            if let Some(headers) = &request.headers {
+                println!("请求头：");
+                for (key, value) in headers.iter() {
+                    println!("{}:{}", key, value);
+                }
+            }
+            if let Some((data, _)) = request.body() {
+                println!("请求体：{}", data);
+            }
         }
-        let start = Instant::now();
+        let req = request.build(client)?;
 
         if let Some(output) = &self.output {
-            let resp = req.send()?.error_for_status()?;
-            let elapsed = start.elapsed().as_millis();
-            println!("请求耗时：{}ms", elapsed);
-            let content = resp.text()?;
-            println!("保存响应到文件：{}", output.display());
-            std::fs::write(output, content)?;
+            let start = Instant::now();
+            let result = Downloader::new(output, self.resume, self.sha256.as_deref()).download(req);
+            self.record(request, None, result.as_ref().err().map(|e| e.to_string()), start.elapsed());
+            result?;
+            redirects.print();
         } else {
-            let resp = req.send()?.error_for_status()?;
-            let elapsed = start.elapsed().as_millis();
-            println!("请求耗时：{}ms", elapsed);
-            println!("状态码：{}", resp.status());
-            println!("响应头：");
-            for x in resp.headers() {
-                if let Ok(val) = x.1.to_str() {
-                    println!("{}:{}", x.0, val)
+            let mut req = req.build()?;
+            // 客户端不自动解压，自行声明并解压gzip，以便展示压缩前后的大小；SSE事件流不压缩
+            if !self.sse {
+                req.headers_mut()
+                    .entry(ACCEPT_ENCODING)
+                    .or_insert(HeaderValue::from_static("gzip, deflate"));
+            }
+            // 协议版本收到响应后才能确定，先保存请求行和请求头
+            let head = self.verbose.then(|| {
+                let mut head = Request::new(req.method().clone(), req.url().clone());
+                *head.headers_mut() = req.headers().clone();
+                head
+            });
+            let start = Instant::now();
+            let resp = client.execute(req);
+            let elapsed = start.elapsed();
+            if let Some(head) = &head {
+                let version = resp.as_ref().map_or(head.version(), |resp| resp.version());
+                print_request_line(head, version);
+            }
+            // 请求失败时也打印重定向链，便于定位在哪一跳出错
+            redirects.print();
+            match &resp {
+                Ok(resp) => self.record(request, Some(resp.status().as_u16()), None, elapsed),
+                Err(e) => self.record(request, None, Some(e.to_string()), elapsed),
+            }
+            // 断言了状态码时，由断言决定结果
+            let resp = if self.expect_status.is_some() {
+                resp?
+            } else {
+                resp?.error_for_status()?
+            };
+            if self.verbose {
+                eprintln!("< {:?} {}", resp.version(), resp.status());
+                for (key, value) in resp.headers() {
+                    eprintln!("< {}: {}", key, value.to_str().unwrap_or_default());
+                }
+                eprintln!("<");
+                eprintln!("* 请求耗时：{}ms", elapsed.as_millis());
+            } else if curl_style {
+                println!("{:?} {}", resp.version(), resp.status());
+                for (key, value) in resp.headers() {
+                    println!("{}: {}", key, value.to_str().unwrap_or_default());
+                }
+                println!();
+            } else {
+                println!("请求耗时：{}ms", elapsed.as_millis());
+                println!("协议：{:?}", resp.version());
+                println!("状态码：{}", resp.status());
+                println!("响应头：");
+                for x in resp.headers() {
+                    if let Ok(val) = x.1.to_str() {
+                        println!("{}:{}", x.0, val)
+                    }
                 }
             }
-            println!("✅ 响应体：");
-            println!("{}", resp.text()?);
+            client_args.check_version(resp.version())?;
+            let is_event_stream = resp
+                .headers()
+                .get(CONTENT_TYPE)
+                .and_then(|value| value.to_str().ok())
+                .is_some_and(|value| value.starts_with("text/event-stream"));
+            if self.sse || is_event_stream {
+                // 事件流没有完整的响应体，开始接收前只检查状态码和响应头
+                let assertions = self.assertions();
+                if assertions
+                    .iter()
+                    .any(|assertion| matches!(assertion, Assertion::Json(..) | Assertion::BodyContains(_)))
+                {
+                    return Err(CliError::AssertionError(
+                        "SSE事件流不支持--expect-json、--expect-body-contains".to_string(),
+                    ));
+                }
+                let failed = assertion::check_all(&assertions, resp.status(), resp.headers(), "");
+                if failed > 0 {
+                    return Err(CliError::AssertionError(format!("{}/{} 个断言失败", failed, assertions.len())));
+                }
+                return sse::stream_events(resp);
+            }
+            let status = resp.status();
+            let headers = resp.headers().clone();
+            // HEAD请求没有响应体，只输出响应头
+            let raw = if request.method == HttpMethod::Head {
+                Default::default()
+            } else {
+                resp.bytes()?
+            };
+            let encoding = headers.get(CONTENT_ENCODING).and_then(|value| value.to_str().ok());
+            let decoded = body::decode(&raw, encoding)?;
+            let bytes = decoded.as_deref().unwrap_or(&raw);
+            if let Some(encoding) = encoding
+                && request.method != HttpMethod::Head
+            {
+                let summary = body::compression_summary(encoding, raw.len(), decoded.as_ref().map(Vec::len));
+                if self.verbose {
+                    eprintln!("* 压缩：{}", summary);
+                } else if !curl_style {
+                    println!("📦 压缩：{}", summary);
+                }
+            }
+            if request.method != HttpMethod::Head {
+                if !curl_style {
+                    println!("✅ 响应体：");
+                }
+                let printer = BodyPrinter {
+                    max_display: self.max_body_display,
+                    hexdump: self.hexdump,
+                    save_binary: self.save_binary,
+                };
+                let content_type = headers.get(CONTENT_TYPE).and_then(|value| value.to_str().ok());
+                printer.print(bytes, content_type, &request.url)?;
+            }
+            let body = String::from_utf8_lossy(bytes);
+
+            let assertions = self.assertions();
+            let failed = assertion::check_all(&assertions, status, &headers, &body);
+            if failed > 0 {
+                return Err(CliError::AssertionError(format!("{}/{} 个断言失败", failed, assertions.len())));
+            }
         }
         Ok(())
     }
 }
+
+/// 按curl -v的格式输出请求行和请求头
+fn print_request_line(req: &Request, version: Version) {
+    let url = req.url();
+    let path = match url.query() {
+        Some(query) => format!("{}?{}", url.path(), query),
+        None => url.path().to_string(),
+    };
+    eprintln!("> {} {} {:?}", req.method(), path, version);
+    if let Some(host) = url.host_str() {
+        match url.port() {
+            Some(port) => eprintln!("> Host: {}:{}", host, port),
+            None => eprintln!("> Host: {}", host),
+        }
+    }
+    for (key, value) in req.headers() {
+        eprintln!("> {}: {}", key, value.to_str().unwrap_or_default());
+    }
+    eprintln!(">");
+}
+
+impl CommandHandler for CurlHandler {
+    fn run(&self) -> Result<(), CliError> {
+        match &self.command {
+            Some(CurlCommand::Bench(bench)) => return bench.run(),
+            Some(CurlCommand::Run(run)) => return run.run(),
+            Some(CurlCommand::History(history)) => return history.run(),
+            Some(CurlCommand::Mock(mock)) => return mock.run(),
+            Some(CurlCommand::Replay(_)) | None => {}
+        }
+        if self.tui {
+            return TuiClient::run(&self.client, self.theme.clone());
+        }
+        let mut client_args = self.client.clone();
+        let mut cookies = self.cookie.clone();
+        let mut request = match (&self.command, &self.from_curl) {
+            // 重放的请求与普通请求走同样的流程，便于复用会话、Cookie和输出
+            (Some(CurlCommand::Replay(replay)), _) => {
+                client_args = replay.client.clone();
+                replay.request()?
+            }
+            (_, Some(command)) => {
+                let parsed = CurlCommandLine::parse(command)?;
+                client_args.insecure |= parsed.insecure;
+                cookies.extend(parsed.cookies.iter().cloned());
+                parsed.into_request()?
+            }
+            (_, None) => self.request.clone().ok_or(CliError::HandlerParamMissError)?,
+        };
+        if self.head {
+            request.method = HttpMethod::Head;
+        }
+        let mut session = self.session.as_deref().map(Session::load).transpose()?;
+        let headers = request.headers.clone().unwrap_or_default();
+        if let Some(session) = &session {
+            request.headers = Some(session.merge_headers(&headers));
+        }
+        if self.print_curl {
+            println!("{}", self.to_curl_command(&request, &client_args, &cookies));
+            return Ok(());
+        }
+        // 重放的请求体是记录下的字面内容，覆盖的-d已在构建重放请求时读取
+        // WebSocket的请求体作为第一条消息整体发送，不使用流式上传
+        let websocket = websocket::is_websocket_url(&request.url);
+        if !matches!(self.command, Some(CurlCommand::Replay(_))) {
+            request.resolve_body(!websocket)?;
+        }
+        if websocket {
+            return websocket::console(&request, &client_args);
+        }
+        if self.sse && !headers.iter().any(|(key, _)| key.eq_ignore_ascii_case("accept")) {
+            request
+                .headers
+                .get_or_insert_with(Vec::new)
+                .push(("Accept".to_string(), "text/event-stream".to_string()));
+        }
+        let jar = Arc::new(self.load_cookies(&request.url, &cookies, session.as_ref())?);
+        let (builder, redirects) = client_args.builder(Some(jar.clone()))?;
+        let client = builder.no_gzip().build()?;
+        let result = self.send(&request, &client, &client_args, &redirects);
+
+        // 无论请求成功与否都保存Cookie，失败的响应同样可能设置Cookie
+        if let Some(path) = &self.cookie_jar {
+            jar.save(path)?;
+        }
+        if let Some(session) = &mut session {
+            session.remember_headers(&headers, &self.session_header);
+            session.save()?;
+            jar.save(&session.cookie_path()?)?;
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+
+    #[test]
+    fn test_print_curl_body() {
+        let handler =
+            CurlHandler::try_parse_from(["curl", "--print-curl", "--data-raw", "@x", "http://a.com"]).unwrap();
+        let request = handler.request.clone().unwrap();
+        let command = handler.to_curl_command(&request, &handler.client, &[]);
+        assert!(command.contains(" --data-raw @x "), "{}", command);
+        let request = CurlCommandLine::parse("curl --data-raw '@x' http://a.com")
+            .unwrap()
+            .into_request()
+            .unwrap();
+        let command = handler.to_curl_command(&request, &handler.client, &[]);
+        assert!(command.contains(" --data-raw @x "), "{}", command);
+        let request = CurlCommandLine::parse("curl -d @x http://a.com")
+            .unwrap()
+            .into_request()
+            .unwrap();
+        let command = handler.to_curl_command(&request, &handler.client, &[]);
+        assert!(command.contains(" -d @x "), "{}", command);
+    }
+
+    #[test]
+    fn test_resolve_body() {
+        let path = std::env::temp_dir().join(format!("fun-cli-{}-body.json", std::process::id()));
+        std::fs::write(&path, "{\"a\":\n1}\n").unwrap();
+        let data = format!("@{}", path.display());
+        let handler = CurlHandler::try_parse_from(["curl", "-d", &data, "http://a.com"]).unwrap();
+        let mut request = handler.request.unwrap();
+        // 未读取时按字面内容发送，集合、历史记录中的请求体不会被当作文件
+        let req = request.build(&Client::new()).unwrap().build().unwrap();
+        assert_eq!(req.body().and_then(|body| body.as_bytes()), Some(data.as_bytes()));
+        request.resolve_body(true).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        // 文件已读入内存，删除后仍可重复构建
+        for _ in 0..2 {
+            let req = request.build(&Client::new()).unwrap().build().unwrap();
+            assert_eq!(req.body().and_then(|body| body.as_bytes()), Some(&b"{\"a\":1}"[..]));
+            assert_eq!(req.headers()[CONTENT_TYPE], "application/json");
+        }
+    }
+
+    #[test]
+    fn test_sse_assertions() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/events", listener.local_addr().unwrap());
+        let server = std::thread::spawn(move || {
+            for _ in 0..3 {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut line = String::new();
+                while reader.read_line(&mut line).unwrap() > 2 {
+                    line.clear();
+                }
+                let response =
+                    "HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nConnection: close\r\n\r\ndata: hi\n\n";
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+        });
+        // 自动识别为事件流时，状态码和响应头断言仍然生效
+        let run = |args: &[&str]| {
+            let args = ["curl", "--no-history"].iter().chain(args).copied().chain([url.as_str()]);
+            CurlHandler::try_parse_from(args).unwrap().run()
+        };
+        assert!(run(&["--expect-status", "201"]).is_err());
+        assert!(run(&["--expect-status", "200", "--expect-header", "content-type"]).is_ok());
+        // 事件流没有完整的响应体，响应体断言直接失败
+        assert!(run(&["--expect-body-contains", "hi"]).is_err());
+        server.join().unwrap();
+        assert!(CurlHandler::try_parse_from(["curl", "--sse", "--expect-json", ".a exists", "http://a.com"]).is_err());
+    }
+}
@@ -21,7 +21,7 @@ impl CombineHandler {
         match cmd {
             Commands::Weather(handler) => Ok(Box::new(handler)),
             Commands::Music(handler) => Ok(Box::new(handler)),
-            Commands::Curl(handler) => Ok(Box::new(handler)),
+            Commands::Curl(handler) => Ok(handler),
             Commands::Game(handler) => Ok(Box::new(handler)),
             Commands::Os(handler) => Ok(Box::new(handler)),
             _ => Err(CliError::NoMatchHandlerError),
@@ -0,0 +1,257 @@
+use crate::impls::http::json_path;
+use crossterm::style::Stylize;
+use reqwest::StatusCode;
+use reqwest::header::HeaderMap;
+use serde_json::Value;
+use std::fmt::{Display, Formatter};
+
+/// 响应断言，用于脚本化的接口检查
+#[derive(Debug, Clone)]
+pub enum Assertion {
+    // 状态码，支持 200、2xx、200,201
+    Status(Vec<String>),
+    // 响应头存在，或值等于给定值
+    Header(String, Option<String>),
+    // JSON路径检查
+    Json(String, JsonCheck),
+    BodyContains(String),
+}
+
+#[derive(Debug, Clone)]
+pub enum JsonCheck {
+    Exists,
+    NotExists,
+    Compare(CompareOp, Value),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CompareOp {
+    Eq,
+    Ne,
+    Gt,
+    Ge,
+    Lt,
+    Le,
+}
+
+impl CompareOp {
+    fn symbol(&self) -> &'static str {
+        match self {
+            CompareOp::Eq => "==",
+            CompareOp::Ne => "!=",
+            CompareOp::Gt => ">",
+            CompareOp::Ge => ">=",
+            CompareOp::Lt => "<",
+            CompareOp::Le => "<=",
+        }
+    }
+
+    fn apply(&self, actual: &Value, expected: &Value) -> bool {
+        match self {
+            CompareOp::Eq => actual == expected,
+            CompareOp::Ne => actual != expected,
+            _ => match (actual.as_f64(), expected.as_f64()) {
+                (Some(a), Some(e)) => match self {
+                    CompareOp::Gt => a > e,
+                    CompareOp::Ge => a >= e,
+                    CompareOp::Lt => a < e,
+                    _ => a <= e,
+                },
+                _ => false,
+            },
+        }
+    }
+}
+
+impl Display for Assertion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Assertion::Status(expected) => write!(f, "status {}", expected.join(",")),
+            Assertion::Header(name, None) => write!(f, "header {} exists", name),
+            Assertion::Header(name, Some(value)) => write!(f, "header {}: {}", name, value),
+            Assertion::Json(path, JsonCheck::Exists) => write!(f, "json {} exists", path),
+            Assertion::Json(path, JsonCheck::NotExists) => write!(f, "json {} not exists", path),
+            Assertion::Json(path, JsonCheck::Compare(op, value)) => {
+                write!(f, "json {} {} {}", path, op.symbol(), value)
+            }
+            Assertion::BodyContains(text) => write!(f, "body contains {:?}", text),
+        }
+    }
+}
+
+pub fn parse_expect_status(status: &str) -> Result<Assertion, String> {
+    let expected = status
+        .split(',')
+        .map(|s| s.trim().to_ascii_lowercase())
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<String>>();
+    let valid =
+        |s: &String| s.len() == 3 && s.chars().enumerate().all(|(i, c)| c.is_ascii_digit() || (i > 0 && c == 'x'));
+    if expected.is_empty() || !expected.iter().all(valid) {
+        return Err(format!("Invalid status: {}，示例：200、2xx、200,201", status));
+    }
+    Ok(Assertion::Status(expected))
+}
+
+pub fn parse_expect_header(header: &str) -> Result<Assertion, String> {
+    match header.split_once(':') {
+        Some((name, value)) if !name.trim().is_empty() => {
+            Ok(Assertion::Header(name.trim().to_string(), Some(value.trim().to_string())))
+        }
+        None if !header.trim().is_empty() => Ok(Assertion::Header(header.trim().to_string(), None)),
+        _ => Err(format!("Invalid header assertion: {}", header)),
+    }
+}
+
+/// 解析JSON断言，格式为`<路径> exists`、`<路径> not exists`或`<路径> <比较符> <值>`
+///
+/// 值按JSON解析，解析失败时视为字符串，如`.data.name == fun`
+pub fn parse_expect_json(expression: &str) -> Result<Assertion, String> {
+    let expression = expression.trim();
+    let (path, rest) = expression
+        .split_once(char::is_whitespace)
+        .ok_or_else(|| format!("Invalid json assertion: {}，示例：'.data.id exists'", expression))?;
+    let rest = rest.trim();
+    let check = match rest {
+        "exists" => JsonCheck::Exists,
+        "not exists" => JsonCheck::NotExists,
+        _ => {
+            let ops = [
+                ("==", CompareOp::Eq),
+                ("!=", CompareOp::Ne),
+                (">=", CompareOp::Ge),
+                ("<=", CompareOp::Le),
+                (">", CompareOp::Gt),
+                ("<", CompareOp::Lt),
+            ];
+            let (op, value) = ops
+                .iter()
+                .find_map(|(symbol, op)| rest.strip_prefix(symbol).map(|value| (*op, value.trim())))
+                .ok_or_else(|| format!("Invalid json assertion: {}", expression))?;
+            let value = serde_json::from_str(value).unwrap_or_else(|_| Value::String(value.to_string()));
+            JsonCheck::Compare(op, value)
+        }
+    };
+    Ok(Assertion::Json(path.to_string(), check))
+}
+
+impl Assertion {
+    /// 校验响应，失败时返回实际值的说明
+    fn check(&self, status: StatusCode, headers: &HeaderMap, body: &str) -> Result<(), String> {
+        match self {
+            Assertion::Status(expected) => {
+                let actual = status.as_u16().to_string();
+                let matched = expected
+                    .iter()
+                    .any(|pattern| pattern.chars().zip(actual.chars()).all(|(p, a)| p == 'x' || p == a));
+                if matched {
+                    Ok(())
+                } else {
+                    Err(format!("actual {}", actual))
+                }
+            }
+            Assertion::Header(name, expected) => {
+                let actual = headers.get(name).map(|value| value.to_str().unwrap_or_default());
+                match (actual, expected) {
+                    (None, _) => Err("header missing".to_string()),
+                    (Some(_), None) => Ok(()),
+                    (Some(actual), Some(expected)) if actual == expected => Ok(()),
+                    (Some(actual), Some(_)) => Err(format!("actual {}", actual)),
+                }
+            }
+            Assertion::Json(path, check) => {
+                let json: Value = serde_json::from_str(body).map_err(|e| format!("body is not json: {}", e))?;
+                let actual = json_path::query(&json, path);
+                match (check, actual) {
+                    (JsonCheck::Exists, Some(_)) | (JsonCheck::NotExists, None) => Ok(()),
+                    (JsonCheck::Exists, None) => Err("path missing".to_string()),
+                    (JsonCheck::NotExists, Some(actual)) => Err(format!("actual {}", actual)),
+                    (JsonCheck::Compare(..), None) => Err("path missing".to_string()),
+                    (JsonCheck::Compare(op, expected), Some(actual)) => {
+                        if op.apply(actual, expected) {
+                            Ok(())
+                        } else {
+                            Err(format!("actual {}", actual))
+                        }
+                    }
+                }
+            }
+            Assertion::BodyContains(text) => {
+                if body.contains(text.as_str()) {
+                    Ok(())
+                } else {
+                    Err("not found in body".to_string())
+                }
+            }
+        }
+    }
+}
+
+/// 执行所有断言并打印结果，返回失败的数量
+pub fn check_all(assertions: &[Assertion], status: StatusCode, headers: &HeaderMap, body: &str) -> usize {
+    if assertions.is_empty() {
+        return 0;
+    }
+    println!("🧪 断言结果：");
+    let mut failed = 0;
+    for assertion in assertions {
+        match assertion.check(status, headers, body) {
+            Ok(()) => println!("  {} {}", "PASS".green().bold(), assertion),
+            Err(reason) => {
+                failed += 1;
+                println!("  {} {} ({})", "FAIL".red().bold(), assertion, reason);
+            }
+        }
+    }
+    failed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    fn check(assertion: Result<Assertion, String>, status: u16, body: &str) -> bool {
+        let mut headers = HeaderMap::new();
+        headers.insert("content-type", HeaderValue::from_static("application/json"));
+        assertion
+            .unwrap()
+            .check(StatusCode::from_u16(status).unwrap(), &headers, body)
+            .is_ok()
+    }
+
+    #[test]
+    fn test_status_assertion() {
+        assert!(check(parse_expect_status("200"), 200, ""));
+        assert!(check(parse_expect_status("2xx"), 204, ""));
+        assert!(check(parse_expect_status("201, 404"), 404, ""));
+        assert!(!check(parse_expect_status("2xx"), 500, ""));
+        assert!(parse_expect_status("abc").is_err());
+        assert!(parse_expect_status("x00").is_err());
+    }
+
+    #[test]
+    fn test_header_and_body_assertion() {
+        assert!(check(parse_expect_header("Content-Type"), 200, ""));
+        assert!(check(parse_expect_header("content-type: application/json"), 200, ""));
+        assert!(!check(parse_expect_header("Content-Type: text/html"), 200, ""));
+        assert!(!check(parse_expect_header("X-Missing"), 200, ""));
+        assert!(check(Ok(Assertion::BodyContains("ok".to_string())), 200, "it is ok"));
+    }
+
+    #[test]
+    fn test_json_assertion() {
+        let body = r#"{"data": {"id": 7, "name": "fun", "tags": []}}"#;
+        assert!(check(parse_expect_json(".data.id exists"), 200, body));
+        assert!(check(parse_expect_json(".data.age not exists"), 200, body));
+        assert!(check(parse_expect_json(".data.id == 7"), 200, body));
+        assert!(check(parse_expect_json(".data.id >= 7"), 200, body));
+        assert!(check(parse_expect_json(".data.name == fun"), 200, body));
+        assert!(check(parse_expect_json(r#".data.name != "cli""#), 200, body));
+        assert!(check(parse_expect_json(".data.tags == []"), 200, body));
+        assert!(!check(parse_expect_json(".data.id < 5"), 200, body));
+        assert!(!check(parse_expect_json(".data.id exists"), 200, "not json"));
+        assert!(parse_expect_json(".data.id").is_err());
+        assert!(parse_expect_json(".data.id ~ 1").is_err());
+    }
+}
@@ -0,0 +1,470 @@
+use crate::error::CliError;
+use crate::impls::curl::RequestArgs;
+use crate::impls::handlers::CommandHandler;
+use crate::impls::http::client::ClientArgs;
+use crate::ui::Coordinate;
+use crate::ui::event::{InputEvent, poll_event};
+use crate::ui::theme::Theme;
+use crate::ui::widget::{List, Panel, Widget};
+use clap::Parser;
+use crossterm::cursor::{Hide, MoveTo, Show};
+use crossterm::event::{KeyCode, KeyModifiers};
+use crossterm::style::{Print, SetBackgroundColor, SetForegroundColor, Stylize};
+use crossterm::terminal::{Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen, size};
+use crossterm::{execute, queue, terminal};
+use reqwest::blocking::Client;
+use std::collections::BTreeMap;
+use std::io::{self, Stdout, Write, stdout};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// 统计面板展示的百分位
+const PERCENTILES: [f64; 6] = [50.0, 75.0, 90.0, 95.0, 99.0, 100.0];
+/// 压测界面所需的最小终端尺寸
+const MIN_WIDTH: u16 = 60;
+const MIN_HEIGHT: u16 = 20;
+
+#[derive(Debug, Parser)]
+pub struct BenchHandler {
+    #[arg(short, long, default_value_t = 10, help = "并发数")]
+    concurrency: usize,
+
+    #[arg(short = 'n', long, default_value_t = 100, help = "请求总数")]
+    requests: usize,
+
+    #[arg(short, long, value_enum, default_value_t = Theme::Cyberpunk, help = "主题/cyberpunk/blackgold/fire/ocean/aurora")]
+    theme: Theme,
+
+    #[command(flatten)]
+    request: RequestArgs,
+
+    #[command(flatten)]
+    client: ClientArgs,
+}
+
+/// 单次请求结果，`status`为空表示请求失败
+struct BenchResult {
+    status: Option<u16>,
+    latency: Duration,
+}
+
+/// 压测统计数据
+struct BenchStats {
+    latencies: Vec<Duration>,
+    status_codes: BTreeMap<u16, usize>,
+    errors: usize,
+    elapsed: Duration,
+}
+
+impl BenchStats {
+    fn new() -> Self {
+        Self {
+            latencies: Vec::new(),
+            status_codes: BTreeMap::new(),
+            errors: 0,
+            elapsed: Duration::ZERO,
+        }
+    }
+
+    fn record(&mut self, result: BenchResult) {
+        match result.status {
+            Some(status) => {
+                *self.status_codes.entry(status).or_insert(0) += 1;
+                self.latencies.push(result.latency);
+            }
+            None => self.errors += 1,
+        }
+    }
+
+    /// 已完成的请求数（含失败）
+    fn completed(&self) -> usize {
+        self.latencies.len() + self.errors
+    }
+
+    fn requests_per_sec(&self) -> f64 {
+        let secs = self.elapsed.as_secs_f64();
+        if secs == 0.0 {
+            0.0
+        } else {
+            self.completed() as f64 / secs
+        }
+    }
+
+    fn average(&self) -> Duration {
+        if self.latencies.is_empty() {
+            return Duration::ZERO;
+        }
+        self.latencies.iter().sum::<Duration>() / self.latencies.len() as u32
+    }
+
+    /// 计算延迟百分位，`percentiles`取值范围0-100
+    fn percentiles(&self, percentiles: &[f64]) -> Vec<Duration> {
+        let mut sorted = self.latencies.clone();
+        sorted.sort();
+        percentiles
+            .iter()
+            .map(|p| {
+                if sorted.is_empty() {
+                    return Duration::ZERO;
+                }
+                let rank = ((p / 100.0) * sorted.len() as f64).ceil() as usize;
+                sorted[rank.clamp(1, sorted.len()) - 1]
+            })
+            .collect()
+    }
+
+    /// 按最小到最大延迟等分为`buckets`个区间，返回每个区间的上界和请求数
+    fn histogram(&self, buckets: usize) -> Vec<(Duration, usize)> {
+        let (Some(min), Some(max)) = (self.latencies.iter().min(), self.latencies.iter().max()) else {
+            return vec![];
+        };
+        let buckets = buckets.max(1);
+        let step = (*max - *min) / buckets as u32;
+        let mut histogram = (1..=buckets)
+            .map(|i| (*min + step * i as u32, 0))
+            .collect::<Vec<(Duration, usize)>>();
+        // 最后一个区间的上界取最大值，避免整除误差漏掉最大值
+        if let Some(last) = histogram.last_mut() {
+            last.0 = *max;
+        }
+        for latency in &self.latencies {
+            let idx = if step.is_zero() {
+                0
+            } else {
+                (((*latency - *min).as_nanos() / step.as_nanos()) as usize).min(buckets - 1)
+            };
+            histogram[idx].1 += 1;
+        }
+        histogram
+    }
+
+    /// 汇总信息，压测过程中和结束后共用
+    fn summary_lines(&self, total: usize, concurrency: usize) -> Vec<String> {
+        let progress = self.completed() as f64 / total.max(1) as f64;
+        let bar_width = 30;
+        let filled = ((progress * bar_width as f64) as usize).min(bar_width);
+        vec![
+            format!(
+                "进度:     {:>5.1}% [{}{}] {}/{}",
+                progress * 100.0,
+                "=".repeat(filled),
+                " ".repeat(bar_width - filled),
+                self.completed(),
+                total
+            ),
+            format!("并发数:   {}", concurrency),
+            format!("耗时:     {:.2}s", self.elapsed.as_secs_f64()),
+            format!("Req/sec:  {:.2}", self.requests_per_sec()),
+            format!("平均延迟: {}", format_duration(self.average())),
+            format!("失败数:   {}", self.errors),
+        ]
+    }
+
+    fn distribution_lines(&self) -> Vec<String> {
+        let mut lines = vec!["延迟百分位:".to_string()];
+        for (p, latency) in PERCENTILES.iter().zip(self.percentiles(&PERCENTILES)) {
+            lines.push(format!("  p{:<5} {:>10}", p, format_duration(latency)));
+        }
+        lines.push(String::new());
+        lines.push("状态码分布:".to_string());
+        for (status, count) in &self.status_codes {
+            lines.push(format!("  [{}] {:>8} 个", status, count));
+        }
+        if self.errors > 0 {
+            lines.push(format!("  [ERR] {:>8} 个", self.errors));
+        }
+        lines
+    }
+}
+
+fn format_duration(duration: Duration) -> String {
+    let millis = duration.as_secs_f64() * 1000.0;
+    if millis >= 1000.0 {
+        format!("{:.2}s", millis / 1000.0)
+    } else {
+        format!("{:.2}ms", millis)
+    }
+}
+
+/// 延迟直方图组件
+struct HistogramWidget {
+    coordinate: Coordinate,
+    width: u16,
+    height: u16,
+    theme: Theme,
+    histogram: Vec<(Duration, usize)>,
+}
+
+impl HistogramWidget {
+    fn new(left_top: Coordinate, right_bottom: Coordinate, theme: Theme, stats: &BenchStats) -> Self {
+        let height = right_bottom.y - left_top.y;
+        Self {
+            width: right_bottom.x - left_top.x,
+            height,
+            coordinate: left_top,
+            theme,
+            // 顶部留出标题和空行
+            histogram: stats.histogram(height.saturating_sub(2) as usize),
+        }
+    }
+}
+
+impl Widget for HistogramWidget {
+    fn coordinate(&self) -> Coordinate {
+        self.coordinate.clone()
+    }
+
+    fn width(&self) -> u16 {
+        self.width
+    }
+
+    fn height(&self) -> u16 {
+        self.height
+    }
+
+    fn render(&self, stdout: &mut Stdout) -> io::Result<()> {
+        let (x, y) = (self.coordinate.x + 2, self.coordinate.y + 2);
+        let max_count = self.histogram.iter().map(|(_, count)| *count).max().unwrap_or(0);
+        // 标签10列 + 分隔符2列 + 计数8列
+        let bar_width = self.width.saturating_sub(24) as usize;
+        for (i, (upper, count)) in self.histogram.iter().enumerate() {
+            let len = if max_count == 0 {
+                0
+            } else {
+                count * bar_width / max_count
+            };
+            queue!(
+                stdout,
+                MoveTo(x, y + i as u16),
+                SetForegroundColor(self.theme.primary_text_color()),
+                Print(format!("{:>10} |", format_duration(*upper))),
+                SetForegroundColor(self.theme.secondary_color()),
+                Print("█".repeat(len)),
+                SetForegroundColor(self.theme.primary_text_color()),
+                Print(format!(" {}", count))
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl BenchHandler {
+    /// 在工作线程中循环发送请求，直到请求总数用完或被中止
+    ///
+    /// 请求体已预先读入内存，每次构建请求不会重复读取文件或标准输入
+    fn worker(
+        &self,
+        client: &Client,
+        request: &RequestArgs,
+        next: &AtomicUsize,
+        stop: &AtomicBool,
+        tx: mpsc::Sender<BenchResult>,
+    ) {
+        while !stop.load(Ordering::Relaxed) && next.fetch_add(1, Ordering::Relaxed) < self.requests {
+            let start = Instant::now();
+            let status = request.build(client).ok().and_then(|req| {
+                req.send()
+                    .and_then(|mut resp| {
+                        // 读完响应体才算请求结束，同时保证连接可以复用
+                        resp.copy_to(&mut io::sink())?;
+                        Ok(resp)
+                    })
+                    .ok()
+                    // 未协商出要求的HTTP/2时按失败统计
+                    .filter(|resp| self.client.check_version(resp.version()).is_ok())
+                    .map(|resp| resp.status().as_u16())
+            });
+            let result = BenchResult {
+                status,
+                latency: start.elapsed(),
+            };
+            if tx.send(result).is_err() {
+                break;
+            }
+        }
+    }
+
+    fn render(&self, stdout: &mut Stdout, stats: &BenchStats, width: u16, height: u16) -> Result<(), CliError> {
+        let half_w = width / 2;
+        let summary_bottom = 8;
+        let mut summary = List::new_with_padding(
+            Coordinate::new(1, 1),
+            Coordinate::new(width - 1, summary_bottom),
+            self.theme.clone(),
+            2,
+        );
+        summary.set_items(stats.summary_lines(self.requests, self.concurrency));
+        let histogram = HistogramWidget::new(
+            Coordinate::new(1, summary_bottom + 2),
+            Coordinate::new(half_w - 1, height - 1),
+            self.theme.clone(),
+            stats,
+        );
+        let mut distribution = List::new_with_padding(
+            Coordinate::new(half_w + 1, summary_bottom + 2),
+            Coordinate::new(width - 1, height - 1),
+            self.theme.clone(),
+            2,
+        );
+        distribution.set_items(stats.distribution_lines());
+
+        let title = format!("Bench {} {}", self.request.method, self.request.url);
+        execute!(stdout, Clear(ClearType::All))?;
+        Panel::new(&title, summary, self.theme.clone()).render(stdout)?;
+        Panel::new("Latency Histogram", histogram, self.theme.clone()).render(stdout)?;
+        Panel::new("Distribution", distribution, self.theme.clone()).render(stdout)?;
+        queue!(stdout, MoveTo(1, height), Print("按q中止压测"))?;
+        stdout.flush()?;
+        Ok(())
+    }
+}
+
+impl CommandHandler for BenchHandler {
+    fn run(&self) -> Result<(), CliError> {
+        if self.concurrency == 0 {
+            return Err(CliError::UnknownError("并发数必须大于0".to_string()));
+        }
+        let (terminal_width, terminal_height) = size()?;
+        if terminal_width < MIN_WIDTH || terminal_height < MIN_HEIGHT {
+            return Err(CliError::UnknownError(format!(
+                "终端尺寸过小，至少需要{}x{}",
+                MIN_WIDTH, MIN_HEIGHT
+            )));
+        }
+        let (width, height) = (terminal_width - 2, terminal_height - 2);
+        // 每个请求都会经过重定向，不记录重定向链，避免长时间压测时内存不断增长
+        let client = self.client.build_untracked()?;
+        let mut request = self.request.clone();
+        request.resolve_body(false)?;
+        // 请求参数有误时直接报错，而不是把每个请求都计为失败
+        request.build(&client)?.build()?;
+        let next = AtomicUsize::new(0);
+        let stop = AtomicBool::new(false);
+        let (tx, rx) = mpsc::channel::<BenchResult>();
+        let mut stats = BenchStats::new();
+        let mut stdout = stdout();
+
+        terminal::enable_raw_mode()?;
+        execute!(
+            stdout,
+            EnterAlternateScreen,
+            SetBackgroundColor(self.theme.background_color()),
+            Hide
+        )?;
+        let start = Instant::now();
+        let result = thread::scope(|scope| -> Result<(), CliError> {
+            for _ in 0..self.concurrency.min(self.requests) {
+                let tx = tx.clone();
+                let (client, request, next, stop) = (&client, &request, &next, &stop);
+                scope.spawn(move || self.worker(client, request, next, stop, tx));
+            }
+            drop(tx);
+            let mut finished = false;
+            while !finished {
+                loop {
+                    match rx.try_recv() {
+                        Ok(result) => stats.record(result),
+                        Err(mpsc::TryRecvError::Empty) => break,
+                        Err(mpsc::TryRecvError::Disconnected) => {
+                            finished = true;
+                            break;
+                        }
+                    }
+                }
+                stats.elapsed = start.elapsed();
+                self.render(&mut stdout, &stats, width, height)?;
+                match poll_event()? {
+                    // 原始模式下Ctrl+C不会产生信号，与q一样停止压测
+                    Some(InputEvent::Key(KeyCode::Char('c'), modifiers))
+                        if modifiers.contains(KeyModifiers::CONTROL) =>
+                    {
+                        stop.store(true, Ordering::Relaxed)
+                    }
+                    Some(InputEvent::Key(KeyCode::Char('q') | KeyCode::Esc, _)) => stop.store(true, Ordering::Relaxed),
+                    _ => {}
+                }
+            }
+            Ok(())
+        });
+        terminal::disable_raw_mode()?;
+        execute!(stdout, LeaveAlternateScreen, Show)?;
+        result?;
+
+        // 退出界面后打印最终结果，便于保存或在CI中查看
+        println!("{}", "📊 压测结果：".green().bold());
+        for line in stats.summary_lines(self.requests, self.concurrency) {
+            println!("{}", line);
+        }
+        for line in stats.distribution_lines() {
+            println!("{}", line);
+        }
+        println!("延迟直方图:");
+        for (upper, count) in stats.histogram(10) {
+            println!("  {:>10} | {}", format_duration(upper), count);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats_with(millis: &[u64]) -> BenchStats {
+        let mut stats = BenchStats::new();
+        for ms in millis {
+            stats.record(BenchResult {
+                status: Some(200),
+                latency: Duration::from_millis(*ms),
+            });
+        }
+        stats
+    }
+
+    #[test]
+    fn test_percentiles() {
+        let stats = stats_with(&(1..=100).collect::<Vec<u64>>());
+        let result = stats.percentiles(&[50.0, 90.0, 99.0, 100.0]);
+        assert_eq!(
+            result,
+            vec![
+                Duration::from_millis(50),
+                Duration::from_millis(90),
+                Duration::from_millis(99),
+                Duration::from_millis(100)
+            ]
+        );
+        assert_eq!(BenchStats::new().percentiles(&[50.0]), vec![Duration::ZERO]);
+    }
+
+    #[test]
+    fn test_histogram() {
+        let stats = stats_with(&[10, 10, 20, 30, 40, 50]);
+        let histogram = stats.histogram(4);
+        assert_eq!(histogram.len(), 4);
+        assert_eq!(histogram.iter().map(|(_, count)| count).sum::<usize>(), 6);
+        assert_eq!(histogram.last().unwrap().0, Duration::from_millis(50));
+        // 所有延迟相同时全部落入第一个区间
+        let same = stats_with(&[5, 5, 5]);
+        assert_eq!(same.histogram(3)[0].1, 3);
+    }
+
+    #[test]
+    fn test_status_distribution() {
+        let mut stats = stats_with(&[1, 2]);
+        stats.record(BenchResult {
+            status: Some(404),
+            latency: Duration::from_millis(3),
+        });
+        stats.record(BenchResult {
+            status: None,
+            latency: Duration::from_millis(4),
+        });
+        assert_eq!(stats.completed(), 4);
+        assert_eq!(stats.errors, 1);
+        assert_eq!(stats.status_codes.get(&200), Some(&2));
+        assert_eq!(stats.status_codes.get(&404), Some(&1));
+    }
+}
@@ -0,0 +1,208 @@
+use crate::error::CliError;
+use crate::utils::format::{format_bytes, hex_dump};
+use crossterm::style::Stylize;
+use flate2::read::{GzDecoder, ZlibDecoder};
+use std::io::{IsTerminal, Read, Write};
+use std::path::{Path, PathBuf};
+use url::Url;
+
+/// 二进制响应体默认预览的字节数
+const BINARY_PREVIEW_SIZE: usize = 256;
+
+/// 根据Content-Type和内容判断响应体是否为二进制
+///
+/// 文本类的Content-Type仍需校验内容，非UTF-8或包含NUL字符的同样视为二进制
+pub fn is_binary(content_type: Option<&str>, bytes: &[u8]) -> bool {
+    let mime = content_type
+        .and_then(|value| value.split(';').next())
+        .map(|value| value.trim().to_ascii_lowercase())
+        .unwrap_or_default();
+    // SVG是文本格式的图片
+    let media = ["image/", "audio/", "video/", "font/"]
+        .iter()
+        .any(|prefix| mime.starts_with(prefix))
+        && mime != "image/svg+xml";
+    let binary_type = media
+        || matches!(
+            mime.as_str(),
+            "application/octet-stream"
+                | "application/zip"
+                | "application/gzip"
+                | "application/x-gzip"
+                | "application/x-tar"
+                | "application/pdf"
+                | "application/wasm"
+                | "application/x-protobuf"
+                | "application/protobuf"
+                | "application/grpc"
+                | "application/msgpack"
+        );
+    binary_type || bytes.contains(&0) || std::str::from_utf8(bytes).is_err()
+}
+
+/// 按Content-Encoding解压响应体，未压缩或编码不支持时返回`None`
+pub fn decode(bytes: &[u8], encoding: Option<&str>) -> Result<Option<Vec<u8>>, CliError> {
+    let mut decoded = Vec::new();
+    match encoding.map(|value| value.trim().to_ascii_lowercase()).as_deref() {
+        Some("gzip") | Some("x-gzip") => GzDecoder::new(bytes).read_to_end(&mut decoded)?,
+        Some("deflate") => ZlibDecoder::new(bytes).read_to_end(&mut decoded)?,
+        _ => return Ok(None),
+    };
+    Ok(Some(decoded))
+}
+
+/// 压缩信息，如`gzip，传输1.2KB，解压后5.6KB（节省78%）`
+pub fn compression_summary(encoding: &str, compressed: usize, decoded: Option<usize>) -> String {
+    match decoded {
+        Some(size) => format!(
+            "{}，传输{}，解压后{}（节省{:.0}%）",
+            encoding,
+            format_bytes(compressed as u64),
+            format_bytes(size as u64),
+            (1.0 - compressed as f64 / size.max(1) as f64).max(0.0) * 100.0
+        ),
+        None => format!("{}，传输{}，不支持解压，原样输出", encoding, format_bytes(compressed as u64)),
+    }
+}
+
+/// 响应体的显示方式
+pub struct BodyPrinter {
+    // 最多显示的字节数
+    pub max_display: Option<usize>,
+    pub hexdump: bool,
+    // 二进制响应体自动保存到当前目录
+    pub save_binary: bool,
+}
+
+impl BodyPrinter {
+    /// 输出响应体：文本直接输出，二进制在终端中显示十六进制预览，重定向到文件或管道时原样输出
+    pub fn print(&self, bytes: &[u8], content_type: Option<&str>, url: &str) -> Result<(), CliError> {
+        if self.hexdump {
+            let limit = self.max_display.unwrap_or(bytes.len());
+            self.print_hex(bytes, limit);
+            return Ok(());
+        }
+        if !is_binary(content_type, bytes) {
+            let text = String::from_utf8_lossy(bytes);
+            match self.max_display {
+                Some(limit) if bytes.len() > limit => {
+                    // 在字符边界处截断
+                    let end = (0..=limit).rev().find(|&idx| text.is_char_boundary(idx)).unwrap_or(0);
+                    println!("{}", &text[..end]);
+                    println!(
+                        "{}",
+                        format!(
+                            "... 已截断，共{}，仅显示前{}",
+                            format_bytes(bytes.len() as u64),
+                            format_bytes(end as u64)
+                        )
+                        .dark_grey()
+                    );
+                }
+                _ => println!("{}", text),
+            }
+            return Ok(());
+        }
+        if self.save_binary {
+            let path = unique_path(&file_name(url));
+            std::fs::write(&path, bytes)?;
+            println!(
+                "💾 二进制响应体（{}）已保存到：{}",
+                format_bytes(bytes.len() as u64),
+                path.display()
+            );
+            return Ok(());
+        }
+        let mut stdout = std::io::stdout();
+        if !stdout.is_terminal() {
+            stdout.write_all(bytes)?;
+            return Ok(());
+        }
+        println!(
+            "{}",
+            format!(
+                "⚠️ 二进制响应体（{}，{}），可使用-o或--save-binary保存到文件，--hexdump查看完整内容",
+                content_type.unwrap_or("未知类型"),
+                format_bytes(bytes.len() as u64)
+            )
+            .yellow()
+        );
+        self.print_hex(bytes, self.max_display.unwrap_or(BINARY_PREVIEW_SIZE));
+        Ok(())
+    }
+
+    fn print_hex(&self, bytes: &[u8], limit: usize) {
+        for line in hex_dump(&bytes[..limit.min(bytes.len())]) {
+            println!("{}", line);
+        }
+        if bytes.len() > limit {
+            println!(
+                "{}",
+                format!(
+                    "... 共{}，仅显示前{}",
+                    format_bytes(bytes.len() as u64),
+                    format_bytes(limit as u64)
+                )
+                .dark_grey()
+            );
+        }
+    }
+}
+
+/// 以URL路径的最后一段作为文件名
+fn file_name(url: &str) -> String {
+    Url::parse(url)
+        .ok()
+        .and_then(|url| url.path_segments()?.next_back().map(str::to_string))
+        .filter(|name| !name.is_empty())
+        .unwrap_or("response.bin".to_string())
+}
+
+/// 文件已存在时追加序号，如`logo(1).png`
+fn unique_path(name: &str) -> PathBuf {
+    let path = Path::new(name);
+    if !path.exists() {
+        return path.to_path_buf();
+    }
+    let (stem, extension) = match name.rsplit_once('.') {
+        Some((stem, extension)) if !stem.is_empty() => (stem, format!(".{}", extension)),
+        _ => (name, String::new()),
+    };
+    (1..)
+        .map(|i| PathBuf::from(format!("{}({}){}", stem, i, extension)))
+        .find(|path| !path.exists())
+        .unwrap_or(path.to_path_buf())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_binary() {
+        assert!(is_binary(Some("image/png"), b"abc"));
+        assert!(is_binary(Some("application/octet-stream; charset=x"), b"abc"));
+        assert!(!is_binary(Some("image/svg+xml"), b"<svg/>"));
+        assert!(!is_binary(Some("application/json"), "{\"名称\":1}".as_bytes()));
+        assert!(is_binary(Some("text/plain"), b"\x1f\x8b\x08\x00"));
+        assert!(is_binary(None, b"a\0b"));
+        assert!(!is_binary(None, b""));
+    }
+
+    #[test]
+    fn test_decode() {
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(&b"hello ".repeat(100)).unwrap();
+        let compressed = encoder.finish().unwrap();
+        let decoded = decode(&compressed, Some("gzip")).unwrap().unwrap();
+        assert_eq!(decoded, b"hello ".repeat(100));
+        assert_eq!(decode(b"abc", Some("br")).unwrap(), None);
+        assert_eq!(decode(b"abc", None).unwrap(), None);
+    }
+
+    #[test]
+    fn test_file_name() {
+        assert_eq!(file_name("https://a.com/img/logo.png?v=1"), "logo.png");
+        assert_eq!(file_name("https://a.com/"), "response.bin");
+    }
+}
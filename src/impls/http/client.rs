@@ -0,0 +1,307 @@
+use crate::error::CliError;
+use crate::impls::http::cookie::CookieJar;
+use clap::{Args, ValueHint};
+use reqwest::blocking::{Client, ClientBuilder};
+use reqwest::redirect::Policy;
+use reqwest::{Certificate, Identity, Proxy, Version};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+// HTTP客户端参数：重定向、超时、代理和证书
+#[derive(Debug, Clone, Args)]
+pub struct ClientArgs {
+    #[arg(long, default_value_t = 10, help = "最大重定向次数")]
+    pub max_redirects: usize,
+
+    #[arg(long, conflicts_with = "max_redirects", help = "不跟随重定向")]
+    pub no_follow: bool,
+
+    #[arg(long, value_name = "SECS", value_parser = parse_seconds, help = "连接超时时间（秒）")]
+    pub connect_timeout: Option<f64>,
+
+    #[arg(long, value_name = "SECS", value_parser = parse_seconds, help = "请求总超时时间（秒）")]
+    pub timeout: Option<f64>,
+
+    #[arg(long, value_hint = ValueHint::Url, help = "代理地址，如 http://127.0.0.1:7890")]
+    pub proxy: Option<String>,
+
+    #[arg(short = 'k', long, help = "跳过TLS证书校验")]
+    pub insecure: bool,
+
+    #[arg(long, value_hint = ValueHint::FilePath, help = "信任的CA证书文件（PEM/DER）")]
+    pub cacert: Option<PathBuf>,
+
+    #[arg(long, requires = "key", value_hint = ValueHint::FilePath, help = "客户端证书文件（PEM）")]
+    pub cert: Option<PathBuf>,
+
+    #[arg(long, requires = "cert", value_hint = ValueHint::FilePath, help = "客户端私钥文件（PKCS#8 PEM）")]
+    pub key: Option<PathBuf>,
+
+    #[arg(long = "http1.1", conflicts_with_all = ["http2", "http2_prior_knowledge"], help = "只使用HTTP/1.1")]
+    pub http1_1: bool,
+
+    #[arg(long, help = "要求使用HTTP/2，HTTPS通过ALPN协商，服务端未协商HTTP/2时请求失败")]
+    pub http2: bool,
+
+    #[arg(long, conflicts_with = "http2", help = "不经协商直接使用HTTP/2，可用于明文的h2c服务")]
+    pub http2_prior_knowledge: bool,
+}
+
+/// 解析秒数，拒绝负数、NaN、无穷大等无法转换为时长的值
+fn parse_seconds(secs: &str) -> Result<f64, String> {
+    let value = secs.trim().parse::<f64>().map_err(|_| format!("Invalid seconds: {}", secs))?;
+    Duration::try_from_secs_f64(value).map_err(|_| format!("Invalid seconds: {}，需为非负的有限数值", secs))?;
+    Ok(value)
+}
+
+pub fn duration(secs: f64) -> Result<Duration, CliError> {
+    Duration::try_from_secs_f64(secs).map_err(|e| CliError::UnknownError(format!("无效的超时时间 {}：{}", secs, e)))
+}
+
+/// 记录请求经过的重定向，每一项为（状态码，跳转前URL，跳转后URL）
+#[derive(Debug, Clone, Default)]
+pub struct RedirectChain {
+    hops: Arc<Mutex<Vec<(u16, String, String)>>>,
+}
+
+impl RedirectChain {
+    fn push(&self, status: u16, from: String, to: String) {
+        if let Ok(mut hops) = self.hops.lock() {
+            hops.push((status, from, to));
+        }
+    }
+
+    pub fn hops(&self) -> Vec<(u16, String, String)> {
+        self.hops.lock().map(|hops| hops.clone()).unwrap_or_default()
+    }
+
+    /// 打印重定向链
+    pub fn print(&self) {
+        let hops = self.hops();
+        if hops.is_empty() {
+            return;
+        }
+        println!("🔀 重定向链：");
+        for (status, from, to) in hops {
+            println!("  [{}] {} -> {}", status, from, to);
+        }
+    }
+}
+
+impl ClientArgs {
+    /// 根据参数构建客户端，同时返回用于记录重定向的链路
+    ///
+    /// 指定`cookies`时由其管理请求和响应中的Cookie
+    pub fn build(&self, cookies: Option<Arc<CookieJar>>) -> Result<(Client, RedirectChain), CliError> {
+        let (builder, chain) = self.builder(cookies)?;
+        Ok((builder.build()?, chain))
+    }
+
+    /// 与[`ClientArgs::build`]相同，返回尚未构建的`ClientBuilder`，便于调用方追加配置
+    pub fn builder(&self, cookies: Option<Arc<CookieJar>>) -> Result<(ClientBuilder, RedirectChain), CliError> {
+        let chain = RedirectChain::default();
+        Ok((self.configure(cookies, Some(chain.clone()))?, chain))
+    }
+
+    /// 构建不记录重定向的客户端，用于压测等大量重复请求的场景，避免重定向链不断增长
+    pub fn build_untracked(&self) -> Result<Client, CliError> {
+        Ok(self.configure(None, None)?.build()?)
+    }
+
+    fn configure(
+        &self,
+        cookies: Option<Arc<CookieJar>>,
+        chain: Option<RedirectChain>,
+    ) -> Result<ClientBuilder, CliError> {
+        let policy = if self.no_follow {
+            Policy::none()
+        } else {
+            let max = self.max_redirects;
+            Policy::custom(move |attempt| {
+                if let Some(chain) = &chain {
+                    let from = attempt.previous().last().map(|url| url.to_string()).unwrap_or_default();
+                    chain.push(attempt.status().as_u16(), from, attempt.url().to_string());
+                }
+                if attempt.previous().len() > max {
+                    attempt.error(format!("超过最大重定向次数：{}", max))
+                } else {
+                    attempt.follow()
+                }
+            })
+        };
+        let mut builder = Client::builder().redirect(policy);
+        if let Some(cookies) = cookies {
+            builder = builder.cookie_provider(cookies);
+        }
+        if let Some(secs) = self.connect_timeout {
+            builder = builder.connect_timeout(duration(secs)?);
+        }
+        // 未指定时不限制总时长（blocking客户端默认30秒），避免长时间的下载和SSE流被中断
+        builder = builder.timeout(self.timeout.map(duration).transpose()?);
+        if let Some(proxy) = &self.proxy {
+            builder = builder.proxy(Proxy::all(proxy)?);
+        }
+        if self.insecure {
+            builder = builder.danger_accept_invalid_certs(true).danger_accept_invalid_hostnames(true);
+        }
+        if let Some(cacert) = &self.cacert {
+            let content = std::fs::read(cacert)?;
+            let cert = Certificate::from_pem(&content).or_else(|_| Certificate::from_der(&content))?;
+            builder = builder.add_root_certificate(cert);
+        }
+        if let (Some(cert), Some(key)) = (&self.cert, &self.key) {
+            let identity = Identity::from_pkcs8_pem(&std::fs::read(cert)?, &std::fs::read(key)?)?;
+            builder = builder.identity(identity);
+        }
+        // 默认通过ALPN协商，HTTPS服务端支持时使用HTTP/2
+        if self.http1_1 {
+            builder = builder.http1_only();
+        } else if self.http2_prior_knowledge {
+            builder = builder.http2_prior_knowledge();
+        }
+        Ok(builder)
+    }
+
+    /// 检查响应的协议版本，reqwest通过ALPN协商失败时会回退到HTTP/1.1，`--http2`时视为失败
+    pub fn check_version(&self, version: Version) -> Result<(), CliError> {
+        if self.http2 && version != Version::HTTP_2 {
+            return Err(CliError::NetRequestError(format!("服务端未协商HTTP/2，实际使用{:?}", version)));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::thread::JoinHandle;
+
+    #[derive(Debug, Parser)]
+    struct Cli {
+        #[command(flatten)]
+        client: ClientArgs,
+    }
+
+    fn parse(args: &[&str]) -> Result<ClientArgs, clap::Error> {
+        Cli::try_parse_from(std::iter::once("fun").chain(args.iter().copied())).map(|cli| cli.client)
+    }
+
+    /// 启动本地HTTP服务，依次处理`count`个连接，按请求行生成响应，返回地址和收到的请求行
+    fn serve(count: usize, respond: fn(&str) -> String) -> (String, JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = format!("http://{}", listener.local_addr().unwrap());
+        let handle = std::thread::spawn(move || {
+            let mut lines = vec![];
+            for _ in 0..count {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                // 读完请求头
+                let mut header = String::new();
+                while reader.read_line(&mut header).unwrap() > 2 {
+                    header.clear();
+                }
+                stream.write_all(respond(line.trim()).as_bytes()).unwrap();
+                lines.push(line.trim().to_string());
+            }
+            lines
+        });
+        (addr, handle)
+    }
+
+    fn redirect(line: &str) -> String {
+        if line.starts_with("GET /start ") {
+            "HTTP/1.1 302 Found\r\nLocation: /end\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".to_string()
+        } else {
+            "HTTP/1.1 200 OK\r\nContent-Length: 2\r\nConnection: close\r\n\r\nok".to_string()
+        }
+    }
+
+    #[test]
+    fn test_parse_timeout() {
+        assert_eq!(parse(&["--timeout", "1.5"]).unwrap().timeout, Some(1.5));
+        assert!(parse(&["--timeout=-1"]).is_err());
+        assert!(parse(&["--connect-timeout", "nan"]).is_err());
+        assert!(parse(&["--connect-timeout", "inf"]).is_err());
+        assert!(parse(&["--timeout", "abc"]).is_err());
+        // 参数之间的约束
+        assert!(parse(&["--no-follow", "--max-redirects", "3"]).is_err());
+        assert!(parse(&["--cert", "client.pem"]).is_err());
+        assert!(parse(&["--http1.1", "--http2"]).is_err());
+        assert!(parse(&["--http1.1", "--http2-prior-knowledge"]).is_err());
+    }
+
+    #[test]
+    fn test_require_http2() {
+        let (addr, handle) = serve(1, redirect);
+        let args = parse(&["--http2"]).unwrap();
+        let (client, _) = args.build(None).unwrap();
+        let resp = client.get(format!("{}/end", addr)).send().unwrap();
+        handle.join().unwrap();
+        // 明文HTTP无法通过ALPN协商，回退到HTTP/1.1
+        assert_eq!(resp.version(), Version::HTTP_11);
+        assert!(args.check_version(resp.version()).is_err());
+        assert!(args.check_version(Version::HTTP_2).is_ok());
+        assert!(parse(&[]).unwrap().check_version(Version::HTTP_11).is_ok());
+    }
+
+    #[test]
+    fn test_follow_redirects() {
+        let (addr, handle) = serve(2, redirect);
+        let (client, chain) = parse(&[]).unwrap().build(None).unwrap();
+        let resp = client.get(format!("{}/start", addr)).send().unwrap();
+        assert_eq!(resp.text().unwrap(), "ok");
+        assert_eq!(handle.join().unwrap(), ["GET /start HTTP/1.1", "GET /end HTTP/1.1"]);
+        assert_eq!(chain.hops(), [(302, format!("{}/start", addr), format!("{}/end", addr))]);
+
+        // 不记录重定向时同样跟随
+        let (addr, handle) = serve(2, redirect);
+        let client = parse(&[]).unwrap().build_untracked().unwrap();
+        let resp = client.get(format!("{}/start", addr)).send().unwrap();
+        assert_eq!(resp.text().unwrap(), "ok");
+        assert_eq!(handle.join().unwrap(), ["GET /start HTTP/1.1", "GET /end HTTP/1.1"]);
+    }
+
+    #[test]
+    fn test_redirect_limits() {
+        let (addr, handle) = serve(1, redirect);
+        let (client, _) = parse(&["--no-follow"]).unwrap().build(None).unwrap();
+        let resp = client.get(format!("{}/start", addr)).send().unwrap();
+        assert_eq!(resp.status().as_u16(), 302);
+        handle.join().unwrap();
+
+        let (addr, handle) = serve(1, redirect);
+        let (client, _) = parse(&["--max-redirects", "0"]).unwrap().build(None).unwrap();
+        let error = client.get(format!("{}/start", addr)).send().unwrap_err();
+        assert!(format!("{:?}", error).contains("超过最大重定向次数"));
+        handle.join().unwrap();
+    }
+
+    #[test]
+    fn test_proxy() {
+        // 经HTTP代理的请求行为完整URL
+        let (addr, handle) = serve(1, redirect);
+        let (client, _) = parse(&["--proxy", &addr]).unwrap().build(None).unwrap();
+        let resp = client.get("http://example.invalid/end").send().unwrap();
+        assert_eq!(resp.text().unwrap(), "ok");
+        assert_eq!(handle.join().unwrap(), ["GET http://example.invalid/end HTTP/1.1"]);
+        assert!(parse(&["--proxy", "not a url"]).unwrap().build(None).is_err());
+    }
+
+    #[test]
+    fn test_tls_options() {
+        assert!(parse(&["-k"]).unwrap().build(None).is_ok());
+        assert!(parse(&["--cacert", "/no/such/ca.pem"]).unwrap().build(None).is_err());
+        let invalid = std::env::temp_dir().join(format!("fun-cli-{}-invalid.pem", std::process::id()));
+        std::fs::write(&invalid, "not a certificate").unwrap();
+        let path = invalid.to_str().unwrap();
+        assert!(parse(&["--cacert", path]).unwrap().build(None).is_err());
+        assert!(parse(&["--cert", path, "--key", path]).unwrap().build(None).is_err());
+        std::fs::remove_file(invalid).unwrap();
+    }
+}
@@ -0,0 +1,388 @@
+use crate::error::CliError;
+use crate::impls::curl::{RequestArgs, parse_method, parse_url};
+use crate::impls::handlers::CommandHandler;
+use crate::impls::http::client::ClientArgs;
+use crate::impls::http::json_path;
+use crate::utils::storage::{data_dir, write_private};
+use clap::{Parser, ValueHint};
+use crossterm::style::Stylize;
+use reqwest::blocking::Client;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+#[derive(Debug, Parser)]
+pub struct RunHandler {
+    #[arg(required = true, value_hint = ValueHint::FilePath, help = "请求集合文件（TOML/YAML）")]
+    collection: PathBuf,
+
+    #[arg(help = "按顺序执行的请求名称，不指定时列出集合中的所有请求")]
+    names: Vec<String>,
+
+    #[arg(short, long, help = "按定义顺序执行集合中的所有请求")]
+    all: bool,
+
+    #[arg(short, long, help = "使用的环境，如 dev/staging")]
+    env: Option<String>,
+
+    #[arg(long = "var", value_name = "NAME=VALUE", value_parser = parse_variable, help = "覆盖变量")]
+    variables: Vec<(String, String)>,
+
+    #[command(flatten)]
+    client: ClientArgs,
+}
+
+/// 请求集合
+///
+/// 变量优先级：`variables` < 当前环境 < 之前提取并保存的变量 < 命令行`--var`
+#[derive(Debug, Deserialize)]
+struct Collection {
+    #[serde(default)]
+    variables: HashMap<String, String>,
+    #[serde(default)]
+    environments: HashMap<String, HashMap<String, String>>,
+    #[serde(default)]
+    requests: Vec<CollectionRequest>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CollectionRequest {
+    name: String,
+    #[serde(default = "default_method")]
+    method: String,
+    url: String,
+    #[serde(default)]
+    headers: BTreeMap<String, String>,
+    body: Option<String>,
+    // JSON请求体，会自动设置Content-Type
+    json: Option<serde_json::Value>,
+    // 从JSON响应中提取变量：变量名 -> JSON路径
+    #[serde(default)]
+    extract: BTreeMap<String, String>,
+}
+
+fn default_method() -> String {
+    "get".to_string()
+}
+
+fn parse_variable(variable: &str) -> Result<(String, String), String> {
+    match variable.split_once('=') {
+        Some((name, value)) if !name.trim().is_empty() => Ok((name.trim().to_string(), value.to_string())),
+        _ => Err(format!("Invalid variable format: {}", variable)),
+    }
+}
+
+impl Collection {
+    fn load(path: &Path) -> Result<Self, CliError> {
+        let content = std::fs::read_to_string(path)?;
+        let extension = path.extension().and_then(|ext| ext.to_str()).unwrap_or_default();
+        let parse_error = |e: String| CliError::FileSysError(format!("请求集合格式错误 {}: {}", path.display(), e));
+        if extension.eq_ignore_ascii_case("yaml") || extension.eq_ignore_ascii_case("yml") {
+            serde_yaml::from_str(&content).map_err(|e| parse_error(e.to_string()))
+        } else {
+            toml::from_str(&content).map_err(|e| parse_error(e.to_string()))
+        }
+    }
+
+    fn request(&self, name: &str) -> Result<&CollectionRequest, CliError> {
+        self.requests
+            .iter()
+            .find(|request| request.name == name)
+            .ok_or_else(|| CliError::UnknownError(format!("请求集合中不存在请求：{}", name)))
+    }
+}
+
+/// 替换模板中的`{{name}}`变量
+fn render(template: &str, variables: &HashMap<String, String>) -> Result<String, CliError> {
+    let mut result = String::new();
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        let end = rest[start..]
+            .find("}}")
+            .ok_or_else(|| CliError::UnknownError(format!("变量缺少结束符}}}}：{}", template)))?;
+        let name = rest[start + 2..start + end].trim();
+        let value = variables
+            .get(name)
+            .ok_or_else(|| CliError::UnknownError(format!("未定义的变量：{}", name)))?;
+        result.push_str(&rest[..start]);
+        result.push_str(value);
+        rest = &rest[start + end + 2..];
+    }
+    result.push_str(rest);
+    Ok(result)
+}
+
+/// 替换JSON中所有字符串值里的变量，变量值中的引号等字符会被正确转义
+fn render_json(value: &serde_json::Value, variables: &HashMap<String, String>) -> Result<serde_json::Value, CliError> {
+    Ok(match value {
+        serde_json::Value::String(s) => serde_json::Value::String(render(s, variables)?),
+        serde_json::Value::Array(list) => serde_json::Value::Array(
+            list.iter()
+                .map(|item| render_json(item, variables))
+                .collect::<Result<Vec<serde_json::Value>, CliError>>()?,
+        ),
+        serde_json::Value::Object(map) => serde_json::Value::Object(
+            map.iter()
+                .map(|(key, item)| Ok((key.clone(), render_json(item, variables)?)))
+                .collect::<Result<serde_json::Map<String, serde_json::Value>, CliError>>()?,
+        ),
+        other => other.clone(),
+    })
+}
+
+impl CollectionRequest {
+    /// 渲染变量后转换为curl的请求参数
+    fn to_request(&self, variables: &HashMap<String, String>) -> Result<RequestArgs, CliError> {
+        let method = parse_method(&self.method).map_err(CliError::UnknownError)?;
+        let url = parse_url(&render(&self.url, variables)?).map_err(CliError::UnknownError)?;
+        let mut headers = self
+            .headers
+            .iter()
+            .map(|(key, value)| Ok((key.clone(), render(value, variables)?)))
+            .collect::<Result<Vec<(String, String)>, CliError>>()?;
+        let data = match (&self.json, &self.body) {
+            (Some(json), _) => {
+                if !headers.iter().any(|(key, _)| key.eq_ignore_ascii_case("content-type")) {
+                    headers.push(("Content-Type".to_string(), "application/json".to_string()));
+                }
+                Some(render_json(json, variables)?.to_string())
+            }
+            (None, Some(body)) => Some(render(body, variables)?),
+            (None, None) => None,
+        };
+        Ok(RequestArgs {
+            url,
+            method,
+            headers: if headers.is_empty() { None } else { Some(headers) },
+            data,
+            data_binary: None,
+            data_raw: None,
+            payload: None,
+        })
+    }
+}
+
+impl RunHandler {
+    /// 提取的变量保存在`~/.fun-cli/collections/<集合名>.<路径摘要>.<环境>.json`，供之后的调用复用
+    fn state_path(&self) -> Result<PathBuf, CliError> {
+        Ok(data_dir("collections")?.join(self.state_file_name()?))
+    }
+
+    /// 不同目录下的同名集合文件按绝对路径的摘要区分，避免互相覆盖提取的变量
+    fn state_file_name(&self) -> Result<String, CliError> {
+        let stem = self
+            .collection
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .unwrap_or("collection");
+        let path = std::fs::canonicalize(&self.collection)?;
+        let digest = format!("{:x}", Sha256::digest(path.to_string_lossy().as_bytes()));
+        let env = self.env.as_deref().unwrap_or("default");
+        Ok(format!("{}.{}.{}.json", stem, &digest[..16], env))
+    }
+
+    fn load_state(&self) -> Result<HashMap<String, String>, CliError> {
+        let path = self.state_path()?;
+        if !path.exists() {
+            return Ok(HashMap::new());
+        }
+        serde_json::from_str(&std::fs::read_to_string(&path)?)
+            .map_err(|e| CliError::FileSysError(format!("变量文件格式错误 {}: {}", path.display(), e)))
+    }
+
+    fn save_state(&self, state: &HashMap<String, String>) -> Result<(), CliError> {
+        let content = serde_json::to_string_pretty(state).map_err(|e| CliError::UnknownError(e.to_string()))?;
+        // 提取的变量通常是令牌，文件只允许当前用户读写
+        write_private(&self.state_path()?, content)
+    }
+
+    /// 合并各个来源的变量
+    fn variables(
+        &self,
+        collection: &Collection,
+        state: &HashMap<String, String>,
+    ) -> Result<HashMap<String, String>, CliError> {
+        let mut variables = collection.variables.clone();
+        if let Some(env) = &self.env {
+            let env_variables = collection
+                .environments
+                .get(env)
+                .ok_or_else(|| CliError::UnknownError(format!("请求集合中不存在环境：{}", env)))?;
+            variables.extend(env_variables.clone());
+        }
+        variables.extend(state.clone());
+        variables.extend(self.variables.iter().cloned());
+        Ok(variables)
+    }
+
+    fn list(&self, collection: &Collection) {
+        println!("📚 请求集合：{}", self.collection.display());
+        for request in &collection.requests {
+            println!("  {:<20} {:<8} {}", request.name, request.method.to_uppercase(), request.url);
+        }
+        if !collection.environments.is_empty() {
+            let mut envs = collection.environments.keys().cloned().collect::<Vec<String>>();
+            envs.sort();
+            println!("🌐 环境：{}", envs.join(", "));
+        }
+    }
+
+    /// 执行单个请求，返回从响应中提取的变量
+    fn execute(
+        &self,
+        client: &Client,
+        request: &CollectionRequest,
+        variables: &HashMap<String, String>,
+    ) -> Result<HashMap<String, String>, CliError> {
+        let args = request.to_request(variables)?;
+        println!("{} [{}] {} {}", "▶".green(), request.name.clone().bold(), args.method, args.url);
+        let start = Instant::now();
+        let resp = args.build(client)?.send()?;
+        self.client.check_version(resp.version())?;
+        let status = resp.status();
+        let elapsed = start.elapsed().as_millis();
+        let body = resp.text()?;
+        println!("状态码：{}  耗时：{}ms", status, elapsed);
+        println!("{}", body);
+        if !status.is_success() {
+            return Err(CliError::NetRequestError(format!(
+                "请求{}失败，状态码：{}",
+                request.name, status
+            )));
+        }
+        let mut extracted = HashMap::new();
+        if request.extract.is_empty() {
+            return Ok(extracted);
+        }
+        let json: serde_json::Value = serde_json::from_str(&body)
+            .map_err(|e| CliError::UnknownError(format!("响应不是合法的JSON，无法提取变量：{}", e)))?;
+        for (name, path) in &request.extract {
+            let value = json_path::query(&json, path)
+                .ok_or_else(|| CliError::UnknownError(format!("响应中不存在路径{}，无法提取变量{}", path, name)))?;
+            let value = json_path::to_plain_string(value);
+            println!("{} {} = {}", "📌 提取变量".cyan(), name, value);
+            extracted.insert(name.clone(), value);
+        }
+        Ok(extracted)
+    }
+}
+
+impl CommandHandler for RunHandler {
+    fn run(&self) -> Result<(), CliError> {
+        let collection = Collection::load(&self.collection)?;
+        let names = if self.all {
+            collection.requests.iter().map(|request| request.name.clone()).collect()
+        } else {
+            self.names.clone()
+        };
+        if names.is_empty() {
+            self.list(&collection);
+            return Ok(());
+        }
+        // 先校验所有请求都存在，避免执行到一半才报错
+        let requests = names
+            .iter()
+            .map(|name| collection.request(name))
+            .collect::<Result<Vec<&CollectionRequest>, CliError>>()?;
+        let (client, _) = self.client.build(None)?;
+        let mut state = self.load_state()?;
+        for request in requests {
+            let variables = self.variables(&collection, &state)?;
+            let extracted = self.execute(&client, request, &variables)?;
+            if !extracted.is_empty() {
+                state.extend(extracted);
+                self.save_state(&state)?;
+            }
+            println!();
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::impls::curl::HttpMethod;
+
+    const COLLECTION: &str = r#"
+[variables]
+base_url = "http://localhost:8080"
+user = "admin"
+
+[environments.staging]
+base_url = "https://staging.example.com"
+
+[[requests]]
+name = "login"
+method = "post"
+url = "{{base_url}}/login"
+json = { user = "{{ user }}", remember = true }
+extract = { token = "data.token" }
+
+[[requests]]
+name = "profile"
+url = "{{base_url}}/me"
+headers = { Authorization = "Bearer {{token}}" }
+"#;
+
+    #[test]
+    fn test_render_collection_request() {
+        let collection: Collection = toml::from_str(COLLECTION).unwrap();
+        let handler = RunHandler::parse_from(["run", "c.toml", "login", "-e", "staging"]);
+        let variables = handler.variables(&collection, &HashMap::new()).unwrap();
+        let login = collection.request("login").unwrap().to_request(&variables).unwrap();
+        assert_eq!(login.url, "https://staging.example.com/login");
+        assert!(matches!(login.method, HttpMethod::Post));
+        assert_eq!(login.data.as_deref(), Some(r#"{"remember":true,"user":"admin"}"#));
+        assert_eq!(
+            login.headers,
+            Some(vec![("Content-Type".to_string(), "application/json".to_string())])
+        );
+
+        // token尚未提取时报错，提取后可用
+        let profile = collection.request("profile").unwrap();
+        assert!(profile.to_request(&variables).is_err());
+        let state = HashMap::from([("token".to_string(), "t1".to_string())]);
+        let variables = handler.variables(&collection, &state).unwrap();
+        let profile = profile.to_request(&variables).unwrap();
+        assert_eq!(
+            profile.headers,
+            Some(vec![("Authorization".to_string(), "Bearer t1".to_string())])
+        );
+    }
+
+    #[test]
+    fn test_render() {
+        let variables = HashMap::from([("a".to_string(), "1\"".to_string())]);
+        assert_eq!(render("x{{a}}y{{ a }}", &variables).unwrap(), "x1\"y1\"");
+        let json = serde_json::json!({"k": ["{{a}}"]});
+        assert_eq!(render_json(&json, &variables).unwrap().to_string(), r#"{"k":["1\""]}"#);
+        assert!(render("{{b}}", &variables).is_err());
+        assert!(render("{{a", &variables).is_err());
+    }
+
+    #[test]
+    fn test_state_file_name() {
+        let dir = std::env::temp_dir().join(format!("fun-cli-{}-collections", std::process::id()));
+        for sub in ["a", "b"] {
+            std::fs::create_dir_all(dir.join(sub)).unwrap();
+            std::fs::write(dir.join(sub).join("api.toml"), COLLECTION).unwrap();
+        }
+        let name = |path: &Path, env: &[&str]| {
+            let args = ["run", path.to_str().unwrap(), "login"].into_iter().chain(env.iter().copied());
+            RunHandler::parse_from(args).state_file_name().unwrap()
+        };
+        let a = name(&dir.join("a").join("api.toml"), &[]);
+        let b = name(&dir.join("b").join("api.toml"), &[]);
+        let a_relative = name(&dir.join("b").join("..").join("a").join("api.toml"), &[]);
+        let a_staging = name(&dir.join("a").join("api.toml"), &["-e", "staging"]);
+        std::fs::remove_dir_all(&dir).unwrap();
+        // 同名文件不共用变量，同一文件的不同写法共用
+        assert!(a.starts_with("api.") && a.ends_with(".default.json"));
+        assert_ne!(a, b);
+        assert_eq!(a, a_relative);
+        assert_eq!(a_staging, a.replace(".default.", ".staging."));
+    }
+}
@@ -0,0 +1,277 @@
+use crate::error::CliError;
+use crate::utils::storage::write_private;
+use reqwest::cookie::CookieStore;
+use reqwest::header::HeaderValue;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+use url::Url;
+
+/// Netscape格式中HttpOnly Cookie的行前缀
+const HTTP_ONLY_PREFIX: &str = "#HttpOnly_";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cookie {
+    domain: String,
+    include_subdomains: bool,
+    path: String,
+    secure: bool,
+    http_only: bool,
+    // 过期时间（Unix时间戳），0表示会话Cookie
+    expires: u64,
+    name: String,
+    value: String,
+}
+
+impl Cookie {
+    /// 解析响应头中的`Set-Cookie`
+    fn parse_set_cookie(header: &str, url: &Url) -> Option<Self> {
+        let mut parts = header.split(';');
+        let (name, value) = parts.next()?.split_once('=')?;
+        let name = name.trim();
+        if name.is_empty() {
+            return None;
+        }
+        let host = url.host_str()?.to_ascii_lowercase();
+        let mut cookie = Cookie {
+            domain: host.clone(),
+            include_subdomains: false,
+            path: Self::default_path(url),
+            secure: false,
+            http_only: false,
+            expires: 0,
+            name: name.to_string(),
+            value: value.trim().to_string(),
+        };
+        let mut max_age = None;
+        for attr in parts {
+            let (key, val) = attr.split_once('=').unwrap_or((attr, ""));
+            let val = val.trim();
+            match key.trim().to_ascii_lowercase().as_str() {
+                "domain" if !val.is_empty() => {
+                    let domain = val.trim_start_matches('.').to_ascii_lowercase();
+                    // 只允许设置为当前域名或其父域名
+                    if host != domain && !host.ends_with(&format!(".{}", domain)) {
+                        return None;
+                    }
+                    cookie.domain = domain;
+                    cookie.include_subdomains = true;
+                }
+                "path" if val.starts_with('/') => cookie.path = val.to_string(),
+                "secure" => cookie.secure = true,
+                "httponly" => cookie.http_only = true,
+                "max-age" => max_age = val.parse::<i64>().ok(),
+                "expires" => {
+                    if let Ok(time) = httpdate::parse_http_date(val) {
+                        cookie.expires = time.duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(1);
+                    }
+                }
+                _ => {}
+            }
+        }
+        // Max-Age优先于Expires，非正数表示立即过期
+        if let Some(max_age) = max_age {
+            cookie.expires = if max_age > 0 { now() + max_age as u64 } else { 1 };
+        }
+        Some(cookie)
+    }
+
+    /// RFC 6265 默认路径：请求路径最后一个`/`之前的部分
+    fn default_path(url: &Url) -> String {
+        match url.path().rfind('/') {
+            Some(0) | None => "/".to_string(),
+            Some(idx) => url.path()[..idx].to_string(),
+        }
+    }
+
+    fn is_expired(&self) -> bool {
+        self.expires != 0 && self.expires <= now()
+    }
+
+    fn matches(&self, url: &Url) -> bool {
+        let Some(host) = url.host_str().map(|host| host.to_ascii_lowercase()) else {
+            return false;
+        };
+        let domain_match =
+            host == self.domain || (self.include_subdomains && host.ends_with(&format!(".{}", self.domain)));
+        let path = url.path();
+        let path_match = path == self.path
+            || (path.starts_with(&self.path) && (self.path.ends_with('/') || path[self.path.len()..].starts_with('/')));
+        domain_match && path_match && (!self.secure || url.scheme() == "https") && !self.is_expired()
+    }
+
+    /// 转换为Netscape格式的一行
+    fn to_netscape_line(&self) -> String {
+        let flag = |b: bool| if b { "TRUE" } else { "FALSE" };
+        let domain = if self.include_subdomains {
+            format!(".{}", self.domain)
+        } else {
+            self.domain.clone()
+        };
+        format!(
+            "{}{}\t{}\t{}\t{}\t{}\t{}\t{}",
+            if self.http_only { HTTP_ONLY_PREFIX } else { "" },
+            domain,
+            flag(self.include_subdomains),
+            self.path,
+            flag(self.secure),
+            self.expires,
+            self.name,
+            self.value
+        )
+    }
+
+    /// 解析Netscape格式的一行，注释和格式错误的行返回None
+    fn from_netscape_line(line: &str) -> Option<Self> {
+        let (line, http_only) = match line.strip_prefix(HTTP_ONLY_PREFIX) {
+            Some(rest) => (rest, true),
+            None => (line, false),
+        };
+        if line.trim().is_empty() || line.starts_with('#') {
+            return None;
+        }
+        let fields: Vec<&str> = line.split('\t').collect();
+        if fields.len() < 7 {
+            return None;
+        }
+        Some(Cookie {
+            domain: fields[0].trim_start_matches('.').to_ascii_lowercase(),
+            include_subdomains: fields[1].eq_ignore_ascii_case("TRUE"),
+            path: fields[2].to_string(),
+            secure: fields[3].eq_ignore_ascii_case("TRUE"),
+            http_only,
+            expires: fields[4].parse().unwrap_or(0),
+            name: fields[5].to_string(),
+            value: fields[6..].join("\t"),
+        })
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0)
+}
+
+/// 支持以Netscape格式读写的Cookie存储，作为reqwest的Cookie提供者
+#[derive(Debug, Default)]
+pub struct CookieJar {
+    cookies: Mutex<Vec<Cookie>>,
+}
+
+impl CookieJar {
+    /// 插入Cookie，域名、路径、名称都相同的旧Cookie会被替换
+    fn insert(&self, cookie: Cookie) {
+        if let Ok(mut cookies) = self.cookies.lock() {
+            cookies.retain(|c| !(c.domain == cookie.domain && c.path == cookie.path && c.name == cookie.name));
+            if !cookie.is_expired() {
+                cookies.push(cookie);
+            }
+        }
+    }
+
+    /// 为指定URL添加`name=value`形式的Cookie
+    pub fn add_raw(&self, raw: &str, url: &Url) -> Result<(), CliError> {
+        let cookie = Cookie::parse_set_cookie(raw, url)
+            .ok_or_else(|| CliError::UnknownError(format!("Cookie格式错误：{}", raw)))?;
+        self.insert(Cookie {
+            path: "/".to_string(),
+            ..cookie
+        });
+        Ok(())
+    }
+
+    /// 从Netscape格式的文件加载Cookie，文件不存在时忽略
+    pub fn load(&self, path: &Path) -> Result<(), CliError> {
+        if !path.exists() {
+            return Ok(());
+        }
+        let content = std::fs::read_to_string(path)?;
+        content
+            .lines()
+            .filter_map(Cookie::from_netscape_line)
+            .for_each(|cookie| self.insert(cookie));
+        Ok(())
+    }
+
+    /// 以Netscape格式保存Cookie，会话Cookie同样保存，过期Cookie被丢弃
+    pub fn save(&self, path: &Path) -> Result<(), CliError> {
+        let mut content = String::from("# Netscape HTTP Cookie File\n# This file was generated by fun-cli\n\n");
+        if let Ok(cookies) = self.cookies.lock() {
+            for cookie in cookies.iter().filter(|c| !c.is_expired()) {
+                content.push_str(&cookie.to_netscape_line());
+                content.push('\n');
+            }
+        }
+        // Cookie可能包含登录凭据，只允许当前用户读写
+        write_private(path, content)
+    }
+}
+
+impl CookieStore for CookieJar {
+    fn set_cookies(&self, cookie_headers: &mut dyn Iterator<Item = &HeaderValue>, url: &Url) {
+        cookie_headers
+            .filter_map(|header| header.to_str().ok())
+            .filter_map(|header| Cookie::parse_set_cookie(header, url))
+            .for_each(|cookie| self.insert(cookie));
+    }
+
+    fn cookies(&self, url: &Url) -> Option<HeaderValue> {
+        let cookies = self.cookies.lock().ok()?;
+        let header = cookies
+            .iter()
+            .filter(|cookie| cookie.matches(url))
+            .map(|cookie| format!("{}={}", cookie.name, cookie.value))
+            .collect::<Vec<String>>()
+            .join("; ");
+        if header.is_empty() {
+            None
+        } else {
+            HeaderValue::from_str(&header).ok()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_cookie_and_match() {
+        let jar = CookieJar::default();
+        let url = Url::parse("https://api.example.com/v1/login").unwrap();
+        let headers = [
+            HeaderValue::from_static("token=abc; Path=/; Domain=example.com; Secure; HttpOnly"),
+            HeaderValue::from_static("lang=zh"),
+            HeaderValue::from_static("evil=1; Domain=other.com"),
+        ];
+        jar.set_cookies(&mut headers.iter(), &url);
+
+        let same_dir = Url::parse("https://api.example.com/v1/users").unwrap();
+        assert_eq!(jar.cookies(&same_dir).unwrap(), "token=abc; lang=zh");
+        // lang的默认路径是/v1，子域名也不共享
+        let other_host = Url::parse("https://www.example.com/").unwrap();
+        assert_eq!(jar.cookies(&other_host).unwrap(), "token=abc");
+        // Secure Cookie不会通过http发送
+        let plain = Url::parse("http://api.example.com/v1/users").unwrap();
+        assert_eq!(jar.cookies(&plain).unwrap(), "lang=zh");
+    }
+
+    #[test]
+    fn test_expire_by_max_age() {
+        let jar = CookieJar::default();
+        let url = Url::parse("http://localhost/").unwrap();
+        jar.set_cookies(&mut [HeaderValue::from_static("sid=1")].iter(), &url);
+        assert!(jar.cookies(&url).is_some());
+        jar.set_cookies(&mut [HeaderValue::from_static("sid=1; Max-Age=0")].iter(), &url);
+        assert!(jar.cookies(&url).is_none());
+    }
+
+    #[test]
+    fn test_netscape_round_trip() {
+        let url = Url::parse("https://example.com/").unwrap();
+        let cookie = Cookie::parse_set_cookie("a=b c; Domain=example.com; Secure; HttpOnly; Max-Age=60", &url).unwrap();
+        let line = cookie.to_netscape_line();
+        assert!(line.starts_with("#HttpOnly_.example.com\tTRUE\t/\tTRUE\t"));
+        assert_eq!(Cookie::from_netscape_line(&line), Some(cookie));
+        assert_eq!(Cookie::from_netscape_line("# comment"), None);
+    }
+}
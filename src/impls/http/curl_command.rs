@@ -0,0 +1,412 @@
+use crate::error::CliError;
+use crate::impls::curl::{HttpMethod, RequestArgs, parse_header, parse_method, parse_url};
+use crate::impls::http::upload::{Payload, RequestBody};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use url::form_urlencoded;
+
+/// 从curl命令行解析出的请求
+#[derive(Debug, Default)]
+pub struct CurlCommandLine {
+    pub url: Option<String>,
+    pub method: Option<HttpMethod>,
+    pub headers: Vec<(String, String)>,
+    // 请求体的各个部分及其来源参数，多个部分以&连接
+    pub data: Vec<(String, DataKind)>,
+    pub cookies: Vec<String>,
+    pub insecure: bool,
+    // -G：将请求体作为查询参数追加到URL
+    pub get: bool,
+}
+
+/// 请求体的来源参数，决定`@`开头的内容是否读取文件
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DataKind {
+    // -d：读取文件时去掉换行符
+    Data,
+    // --data-binary：读取文件时原样发送
+    Binary,
+    // --data-raw、--data-urlencode：按字面内容发送
+    Raw,
+}
+
+/// 不带参数、解析时直接忽略的curl选项
+const IGNORED_FLAGS: [&str; 16] = [
+    "-s",
+    "--silent",
+    "-S",
+    "--show-error",
+    "-L",
+    "--location",
+    "-v",
+    "--verbose",
+    "-i",
+    "--include",
+    "--compressed",
+    "--http1.1",
+    "--http2",
+    "-f",
+    "--fail",
+    "-#",
+];
+
+impl CurlCommandLine {
+    /// 解析从浏览器开发者工具等处复制的curl命令
+    pub fn parse(command: &str) -> Result<Self, CliError> {
+        let args = shell_split(command)?;
+        let mut args = args.into_iter().peekable();
+        if args.peek().map(|arg| arg == "curl").unwrap_or(false) {
+            args.next();
+        }
+        let mut parsed = CurlCommandLine::default();
+        while let Some(arg) = args.next() {
+            // 拆分 --opt=value 和 -Xvalue 两种写法
+            let (flag, inline) = if arg.starts_with("--") {
+                match arg.split_once('=') {
+                    Some((flag, value)) => (flag.to_string(), Some(value.to_string())),
+                    None => (arg.clone(), None),
+                }
+            } else if arg.starts_with('-') && arg.len() > 2 && arg[1..].starts_with(['X', 'H', 'd', 'u', 'b', 'A', 'e'])
+            {
+                (arg[..2].to_string(), Some(arg[2..].to_string()))
+            } else {
+                (arg.clone(), None)
+            };
+            let mut value = |name: &str| {
+                inline
+                    .clone()
+                    .or_else(|| args.next())
+                    .ok_or_else(|| CliError::UnknownError(format!("curl参数{}缺少值", name)))
+            };
+            match flag.as_str() {
+                "-X" | "--request" => {
+                    let method = value(&flag)?;
+                    parsed.method = Some(parse_method(&method).map_err(CliError::UnknownError)?);
+                }
+                "-H" | "--header" => {
+                    parsed
+                        .headers
+                        .push(parse_header(&value(&flag)?).map_err(CliError::UnknownError)?);
+                }
+                "-d" | "--data" | "--data-ascii" => parsed.data.push((value(&flag)?, DataKind::Data)),
+                "--data-raw" => parsed.data.push((value(&flag)?, DataKind::Raw)),
+                "--data-binary" => parsed.data.push((value(&flag)?, DataKind::Binary)),
+                "--data-urlencode" => parsed.data.push((url_encode_data(&value(&flag)?), DataKind::Raw)),
+                "-u" | "--user" => {
+                    let credential = STANDARD.encode(value(&flag)?);
+                    parsed
+                        .headers
+                        .push(("Authorization".to_string(), format!("Basic {}", credential)));
+                }
+                "-b" | "--cookie" => parsed.cookies.push(value(&flag)?),
+                "-A" | "--user-agent" => parsed.headers.push(("User-Agent".to_string(), value(&flag)?)),
+                "-e" | "--referer" => parsed.headers.push(("Referer".to_string(), value(&flag)?)),
+                "--url" => parsed.url = Some(value(&flag)?),
+                "-k" | "--insecure" => parsed.insecure = true,
+                "-G" | "--get" => parsed.get = true,
+                "-I" | "--head" => parsed.method = Some(HttpMethod::Head),
+                flag if IGNORED_FLAGS.contains(&flag) => {}
+                // 组合的短选项，如 -sSL、-sk
+                flag if flag.starts_with('-') && !flag.starts_with("--") && flag.len() > 2 => {
+                    for c in flag[1..].chars() {
+                        match c {
+                            'k' => parsed.insecure = true,
+                            'G' => parsed.get = true,
+                            'I' => parsed.method = Some(HttpMethod::Head),
+                            c if IGNORED_FLAGS.contains(&format!("-{}", c).as_str()) => {}
+                            c => return Err(CliError::UnknownError(format!("不支持的curl参数：-{}", c))),
+                        }
+                    }
+                }
+                flag if flag.starts_with('-') => {
+                    return Err(CliError::UnknownError(format!("不支持的curl参数：{}", flag)));
+                }
+                _ => parsed.url = Some(arg),
+            }
+        }
+        Ok(parsed)
+    }
+
+    /// 转换为请求参数，与curl一致：有请求体且未指定请求方式时使用POST
+    pub fn into_request(self) -> Result<RequestArgs, CliError> {
+        let url = self
+            .url
+            .ok_or_else(|| CliError::UnknownError("curl命令中缺少URL".to_string()))?;
+        let mut url = parse_url(&url).map_err(CliError::UnknownError)?;
+        // 只有一个部分时保留原样，由请求参数读取文件，可推断Content-Type、流式上传大文件；
+        // 多个部分时逐个读取@开头的部分再连接，结果按字面内容发送
+        let (data, kind) = match self.data.as_slice() {
+            [] => (None, DataKind::Raw),
+            [(data, kind)] if !self.get => (Some(data.clone()), *kind),
+            parts => {
+                let parts = parts
+                    .iter()
+                    .map(|(data, kind)| read_part(data, *kind))
+                    .collect::<Result<Vec<_>, _>>()?;
+                (Some(parts.join("&")), DataKind::Raw)
+            }
+        };
+        let (method, data) = match (self.get, data) {
+            (true, Some(data)) => {
+                url = format!("{}{}{}", url, if url.contains('?') { '&' } else { '?' }, data);
+                (self.method.unwrap_or(HttpMethod::Get), None)
+            }
+            (_, Some(data)) => (self.method.unwrap_or(HttpMethod::Post), Some(data)),
+            (_, None) => (self.method.unwrap_or(HttpMethod::Get), None),
+        };
+        let (data, data_binary, data_raw) = match kind {
+            DataKind::Data => (data, None, None),
+            DataKind::Binary => (None, data, None),
+            DataKind::Raw => (None, None, data),
+        };
+        Ok(RequestArgs {
+            url,
+            method,
+            headers: if self.headers.is_empty() {
+                None
+            } else {
+                Some(self.headers)
+            },
+            data,
+            data_binary,
+            data_raw,
+            payload: None,
+        })
+    }
+}
+
+/// 读取请求体的一个部分，`-d`、`--data-binary`的`@file`、`@-`读取文件或标准输入
+fn read_part(data: &str, kind: DataKind) -> Result<String, CliError> {
+    if kind == DataKind::Raw {
+        return Ok(data.to_string());
+    }
+    match Payload::parse(data).read(kind == DataKind::Binary, false)? {
+        RequestBody::Bytes(bytes) => String::from_utf8(bytes)
+            .map_err(|_| CliError::UnknownError(format!("请求体{}不是文本，无法与其他部分连接", data))),
+        RequestBody::Stream { .. } => unreachable!("不使用流式读取"),
+    }
+}
+
+/// `--data-urlencode`：`name=value`只编码value部分，否则整体编码
+fn url_encode_data(data: &str) -> String {
+    let encode = |s: &str| form_urlencoded::byte_serialize(s.as_bytes()).collect::<String>();
+    match data.split_once('=') {
+        Some((name, value)) => format!("{}={}", name, encode(value)),
+        None => encode(data),
+    }
+}
+
+/// 按照shell规则拆分命令行，支持单引号、双引号、`$'...'`、反斜杠转义和续行
+pub fn shell_split(command: &str) -> Result<Vec<String>, CliError> {
+    let mut args = vec![];
+    let mut current = String::new();
+    // 区分空字符串参数（如 -d ''）和参数之间的空白
+    let mut has_token = false;
+    let mut chars = command.chars().peekable();
+    let unclosed = || CliError::UnknownError("curl命令中的引号未闭合".to_string());
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' => {
+                has_token = true;
+                loop {
+                    match chars.next().ok_or_else(unclosed)? {
+                        '\'' => break,
+                        c => current.push(c),
+                    }
+                }
+            }
+            '"' => {
+                has_token = true;
+                loop {
+                    match chars.next().ok_or_else(unclosed)? {
+                        '"' => break,
+                        '\\' => match chars.next().ok_or_else(unclosed)? {
+                            c @ ('"' | '\\' | '$' | '`') => current.push(c),
+                            '\n' => {}
+                            c => {
+                                current.push('\\');
+                                current.push(c);
+                            }
+                        },
+                        c => current.push(c),
+                    }
+                }
+            }
+            '$' if chars.peek() == Some(&'\'') => {
+                chars.next();
+                has_token = true;
+                loop {
+                    match chars.next().ok_or_else(unclosed)? {
+                        '\'' => break,
+                        '\\' => match chars.next().ok_or_else(unclosed)? {
+                            'n' => current.push('\n'),
+                            't' => current.push('\t'),
+                            'r' => current.push('\r'),
+                            'x' => {
+                                let hex = (0..2).filter_map(|_| chars.next_if(|c| c.is_ascii_hexdigit())).collect();
+                                current.push(parse_code_point(hex)?);
+                            }
+                            'u' => {
+                                let hex = (0..4).filter_map(|_| chars.next_if(|c| c.is_ascii_hexdigit())).collect();
+                                current.push(parse_code_point(hex)?);
+                            }
+                            c => current.push(c),
+                        },
+                        c => current.push(c),
+                    }
+                }
+            }
+            '\\' => match chars.next() {
+                // 行尾的反斜杠表示续行
+                Some('\n') | Some('\r') | None => {}
+                Some(c) => {
+                    has_token = true;
+                    current.push(c);
+                }
+            },
+            c if c.is_whitespace() => {
+                if has_token {
+                    args.push(std::mem::take(&mut current));
+                    has_token = false;
+                }
+            }
+            c => {
+                has_token = true;
+                current.push(c);
+            }
+        }
+    }
+    if has_token {
+        args.push(current);
+    }
+    Ok(args)
+}
+
+fn parse_code_point(hex: String) -> Result<char, CliError> {
+    u32::from_str_radix(&hex, 16)
+        .ok()
+        .and_then(char::from_u32)
+        .ok_or_else(|| CliError::UnknownError(format!("无效的转义字符：{}", hex)))
+}
+
+/// 按照shell规则为参数加引号，仅在需要时使用单引号
+pub fn shell_quote(arg: &str) -> String {
+    let safe = !arg.is_empty() && arg.chars().all(|c| c.is_ascii_alphanumeric() || "-_./:@%+=,".contains(c));
+    if safe {
+        arg.to_string()
+    } else {
+        format!("'{}'", arg.replace('\'', "'\\''"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::blocking::Client;
+
+    #[test]
+    fn test_shell_split() {
+        let args =
+            shell_split("curl 'http://a.com/x?y=1' \\\n  -H \"A: \\\"b\\\"\" --data-raw $'{\\'k\\':\\n1}' -d ''")
+                .unwrap();
+        assert_eq!(
+            args,
+            vec![
+                "curl",
+                "http://a.com/x?y=1",
+                "-H",
+                "A: \"b\"",
+                "--data-raw",
+                "{'k':\n1}",
+                "-d",
+                ""
+            ]
+        );
+        assert!(shell_split("curl 'http://a.com").is_err());
+    }
+
+    #[test]
+    fn test_parse_devtools_command() {
+        let command = r#"curl 'https://api.example.com/login' \
+  -H 'accept: application/json' \
+  -H 'content-type: application/json' \
+  -b 'sid=1; lang=zh' \
+  -u 'admin:123' \
+  --data-raw '{"name":"fun"}' \
+  --compressed -sSk"#;
+        let parsed = CurlCommandLine::parse(command).unwrap();
+        assert_eq!(parsed.cookies, vec!["sid=1; lang=zh"]);
+        assert!(parsed.insecure);
+        let request = parsed.into_request().unwrap();
+        assert_eq!(request.url, "https://api.example.com/login");
+        assert!(matches!(request.method, HttpMethod::Post));
+        assert_eq!(request.body(), Some((r#"{"name":"fun"}"#, false)));
+        let headers = request.headers.unwrap();
+        assert_eq!(headers[0], ("accept".to_string(), "application/json".to_string()));
+        assert_eq!(headers[2], ("Authorization".to_string(), "Basic YWRtaW46MTIz".to_string()));
+    }
+
+    #[test]
+    fn test_parse_data_raw() {
+        let request = CurlCommandLine::parse("curl --data-raw '@x' http://a.com")
+            .unwrap()
+            .into_request()
+            .unwrap();
+        assert!(request.data.is_none());
+        assert_eq!(request.data_raw.as_deref(), Some("@x"));
+        // 请求体按字面内容发送，不读取文件x
+        let body = request.build(&Client::new()).unwrap().build().unwrap();
+        assert_eq!(body.body().and_then(|body| body.as_bytes()), Some(&b"@x"[..]));
+        let request = CurlCommandLine::parse("curl -d @x http://a.com")
+            .unwrap()
+            .into_request()
+            .unwrap();
+        assert_eq!(request.data.as_deref(), Some("@x"));
+    }
+
+    #[test]
+    fn test_parse_mixed_data() {
+        let path = std::env::temp_dir().join(format!("fun-cli-{}-mixed-data", std::process::id()));
+        std::fs::write(&path, "b=2\n").unwrap();
+        let parse = |command: String| CurlCommandLine::parse(&command).unwrap().into_request();
+        // 每个部分按各自的参数处理，与先后顺序无关
+        let request = parse(format!("curl --data-raw @x -d @{} http://a.com", path.display()));
+        let binary = parse(format!("curl -d a=1 --data-binary @{} http://a.com", path.display()));
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(request.unwrap().data_raw.as_deref(), Some("@x&b=2"));
+        assert_eq!(binary.unwrap().data_raw.as_deref(), Some("a=1&b=2\n"));
+        let request = parse("curl -d a --data-raw @x http://a.com".to_string()).unwrap();
+        assert!(request.data.is_none());
+        assert_eq!(request.data_raw.as_deref(), Some("a&@x"));
+        assert!(parse("curl --data-raw a -d @no-such-file http://a.com".to_string()).is_err());
+    }
+
+    #[test]
+    fn test_parse_get_with_data() {
+        let parsed = CurlCommandLine::parse("curl -G --data-urlencode 'q=a b' -XGET http://a.com/s?x=1").unwrap();
+        let request = parsed.into_request().unwrap();
+        assert_eq!(request.url, "http://a.com/s?x=1&q=a+b");
+        assert!(request.data.is_none());
+        assert!(CurlCommandLine::parse("curl --unknown http://a.com").is_err());
+    }
+
+    #[test]
+    fn test_parse_any_method() {
+        let request = CurlCommandLine::parse("curl -X propfind http://a.com/dav")
+            .unwrap()
+            .into_request()
+            .unwrap();
+        assert_eq!(request.method, HttpMethod::Custom("PROPFIND".to_string()));
+        let request = CurlCommandLine::parse("curl -sI http://a.com").unwrap().into_request().unwrap();
+        assert_eq!(request.method, HttpMethod::Head);
+        assert!(CurlCommandLine::parse("curl -X 'GET X' http://a.com").is_err());
+    }
+
+    #[test]
+    fn test_shell_quote() {
+        assert_eq!(shell_quote("https://a.com/x"), "https://a.com/x");
+        assert_eq!(shell_quote("a b"), "'a b'");
+        assert_eq!(shell_quote("it's"), "'it'\\''s'");
+        assert_eq!(shell_quote(""), "''");
+    }
+}
@@ -0,0 +1,319 @@
+use crate::error::CliError;
+use crate::utils::format::format_bytes;
+use crossterm::queue;
+use crossterm::style::{Print, Stylize};
+use crossterm::terminal::{Clear, ClearType};
+use reqwest::StatusCode;
+use reqwest::blocking::{RequestBuilder, Response};
+use reqwest::header::{CONTENT_RANGE, RANGE};
+use sha2::{Digest, Sha256};
+use std::fs::{File, OpenOptions};
+use std::io::{BufWriter, Read, Write, stdout};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+/// 进度条宽度
+const BAR_WIDTH: usize = 40;
+/// 进度刷新间隔
+const REFRESH_INTERVAL: Duration = Duration::from_millis(100);
+
+/// 流式下载器：边读边写入文件，支持断点续传和SHA256校验
+pub struct Downloader<'a> {
+    output: &'a Path,
+    resume: bool,
+    sha256: Option<&'a str>,
+}
+
+impl<'a> Downloader<'a> {
+    pub fn new(output: &'a Path, resume: bool, sha256: Option<&'a str>) -> Self {
+        Self { output, resume, sha256 }
+    }
+
+    /// 发送请求并将响应体写入文件
+    pub fn download(&self, req: RequestBuilder) -> Result<(), CliError> {
+        let existing = if self.resume {
+            std::fs::metadata(self.output).map(|meta| meta.len()).unwrap_or(0)
+        } else {
+            0
+        };
+        // 续传的范围与本地文件对不上时，用不带Range的请求重新下载
+        let fresh = req.try_clone();
+        let req = if existing > 0 {
+            println!("⏩ 断点续传，已下载：{}", format_bytes(existing));
+            req.header(RANGE, format!("bytes={}-", existing))
+        } else {
+            req
+        };
+        let start = Instant::now();
+        let resp = req.send()?;
+        println!("请求耗时：{}ms", start.elapsed().as_millis());
+        println!("状态码：{}", resp.status());
+        if existing > 0 {
+            let (range_start, range_total) = Self::content_range(&resp);
+            let restart = match resp.status() {
+                // 请求范围超出文件大小，且本地文件大小与服务器一致，说明已下载完整
+                StatusCode::RANGE_NOT_SATISFIABLE if range_total == Some(existing) => {
+                    println!("✅ 文件已下载完成：{}", self.output.display());
+                    return self.verify();
+                }
+                StatusCode::RANGE_NOT_SATISFIABLE => Some("本地文件与服务器文件大小不一致"),
+                StatusCode::PARTIAL_CONTENT if range_start != Some(existing) => {
+                    Some("服务器返回的范围与本地文件不衔接")
+                }
+                _ => None,
+            };
+            if let Some(reason) = restart {
+                println!("⚠️ {}，重新下载", reason);
+                let fresh = fresh.ok_or_else(|| CliError::UnknownError("请求无法重新发送".to_string()))?;
+                return Downloader::new(self.output, false, self.sha256).download(fresh);
+            }
+        }
+        let resp = resp.error_for_status()?;
+        let append = existing > 0 && resp.status() == StatusCode::PARTIAL_CONTENT;
+        if existing > 0 && !append {
+            println!("⚠️ 服务器不支持断点续传，重新下载");
+        }
+        let offset = if append { existing } else { 0 };
+        let total = Self::content_range(&resp).1.or(resp.content_length().map(|len| len + offset));
+        let file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .append(append)
+            .truncate(!append)
+            .open(self.output)?;
+        println!("💾 保存响应到文件：{}", self.output.display());
+        Self::stream_to_file(resp, BufWriter::new(file), offset, total)?;
+        self.verify()
+    }
+
+    /// 响应的`Content-Range`中的起始位置和文件总大小
+    fn content_range(resp: &Response) -> (Option<u64>, Option<u64>) {
+        match resp.headers().get(CONTENT_RANGE).and_then(|value| value.to_str().ok()) {
+            Some(range) => parse_content_range(range),
+            None => (None, None),
+        }
+    }
+
+    fn stream_to_file(
+        mut resp: Response,
+        mut writer: BufWriter<File>,
+        offset: u64,
+        total: Option<u64>,
+    ) -> Result<(), CliError> {
+        let mut buffer = vec![0u8; 64 * 1024];
+        let mut downloaded = 0u64;
+        let start = Instant::now();
+        let mut last_refresh = Instant::now();
+        loop {
+            let len = resp.read(&mut buffer)?;
+            if len == 0 {
+                break;
+            }
+            writer.write_all(&buffer[..len])?;
+            downloaded += len as u64;
+            if last_refresh.elapsed() >= REFRESH_INTERVAL {
+                Self::render_progress(offset + downloaded, total, downloaded, start.elapsed())?;
+                last_refresh = Instant::now();
+            }
+        }
+        writer.flush()?;
+        Self::render_progress(offset + downloaded, total, downloaded, start.elapsed())?;
+        println!();
+        Ok(())
+    }
+
+    /// 渲染进度条：`current`为文件当前大小，`downloaded`为本次下载的字节数，用于计算速度
+    fn render_progress(current: u64, total: Option<u64>, downloaded: u64, elapsed: Duration) -> Result<(), CliError> {
+        let secs = elapsed.as_secs_f64();
+        let speed = if secs > 0.0 { downloaded as f64 / secs } else { 0.0 };
+        let line = match total {
+            Some(total) if total > 0 => {
+                let progress = (current as f64 / total as f64).min(1.0);
+                let filled = (progress * BAR_WIDTH as f64) as usize;
+                let eta = if speed > 0.0 {
+                    (total.saturating_sub(current) as f64 / speed) as u64
+                } else {
+                    0
+                };
+                format!(
+                    "{:>5.1}% [{}{}] {}/{} {}/s ETA {:02}:{:02}",
+                    progress * 100.0,
+                    "=".repeat(filled),
+                    " ".repeat(BAR_WIDTH - filled),
+                    format_bytes(current),
+                    format_bytes(total),
+                    format_bytes(speed as u64),
+                    eta / 60,
+                    eta % 60
+                )
+            }
+            _ => format!("已下载 {} {}/s", format_bytes(current), format_bytes(speed as u64)),
+        };
+        let mut stdout = stdout();
+        queue!(stdout, Clear(ClearType::CurrentLine), Print("\r"), Print(line))?;
+        stdout.flush()?;
+        Ok(())
+    }
+
+    /// 校验文件的SHA256摘要
+    fn verify(&self) -> Result<(), CliError> {
+        let Some(expected) = self.sha256 else {
+            return Ok(());
+        };
+        let mut file = File::open(self.output)?;
+        let mut hasher = Sha256::new();
+        let mut buffer = vec![0u8; 64 * 1024];
+        loop {
+            let len = file.read(&mut buffer)?;
+            if len == 0 {
+                break;
+            }
+            hasher.update(&buffer[..len]);
+        }
+        let actual = format!("{:x}", hasher.finalize());
+        if actual.eq_ignore_ascii_case(expected.trim()) {
+            println!("{}{}", "✅ SHA256校验通过：".green(), actual);
+            Ok(())
+        } else {
+            Err(CliError::UnknownError(format!(
+                "SHA256校验失败，期望：{}，实际：{}",
+                expected, actual
+            )))
+        }
+    }
+}
+
+/// 从`Content-Range: bytes 100-999/1000`中解析起始位置和文件总大小
+///
+/// 416响应的格式为`bytes */1000`，没有起始位置；总大小未知（`*`）时返回None
+fn parse_content_range(range: &str) -> (Option<u64>, Option<u64>) {
+    let range = range.trim().strip_prefix("bytes").unwrap_or(range).trim();
+    let (span, total) = range.split_once('/').unwrap_or((range, "*"));
+    let start = span.split_once('-').and_then(|(start, _)| start.trim().parse().ok());
+    (start, total.trim().parse().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::blocking::Client;
+    use std::io::{BufRead, BufReader};
+    use std::net::TcpListener;
+    use std::path::PathBuf;
+
+    /// 测试用的临时文件，写入指定内容
+    fn temp_file(name: &str, content: &[u8]) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("fun-cli-{}-{}", std::process::id(), name));
+        std::fs::write(&path, content).unwrap();
+        path
+    }
+
+    /// 启动HTTP服务，每个连接依次返回一个响应，返回地址和每次收到的请求头
+    fn serve(responses: &'static [&'static str]) -> (String, std::thread::JoinHandle<Vec<Vec<String>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = format!("http://{}/file", listener.local_addr().unwrap());
+        let handle = std::thread::spawn(move || {
+            let mut requests = vec![];
+            for response in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let headers = BufReader::new(stream.try_clone().unwrap())
+                    .lines()
+                    .map_while(Result::ok)
+                    .take_while(|line| !line.is_empty())
+                    .collect();
+                stream.write_all(response.as_bytes()).unwrap();
+                requests.push(headers);
+            }
+            requests
+        });
+        (addr, handle)
+    }
+
+    /// 以断点续传方式下载，返回下载结果、文件内容和每次请求是否带了Range
+    fn resume(name: &str, local: &[u8], responses: &'static [&'static str]) -> (bool, Vec<u8>, Vec<bool>) {
+        let path = temp_file(name, local);
+        let (url, handle) = serve(responses);
+        let result = Downloader::new(&path, true, None).download(Client::new().get(url));
+        let ranges = handle
+            .join()
+            .unwrap()
+            .iter()
+            .map(|headers| headers.iter().any(|header| header.to_lowercase().starts_with("range: bytes=")))
+            .collect();
+        let content = std::fs::read(&path).unwrap();
+        std::fs::remove_file(path).unwrap();
+        (result.is_ok(), content, ranges)
+    }
+
+    #[test]
+    fn test_parse_content_range() {
+        assert_eq!(parse_content_range("bytes 100-999/1000"), (Some(100), Some(1000)));
+        assert_eq!(parse_content_range("bytes 0-99/*"), (Some(0), None));
+        assert_eq!(parse_content_range("bytes */1000"), (None, Some(1000)));
+        assert_eq!(parse_content_range("bytes"), (None, None));
+    }
+
+    #[test]
+    fn test_resume_already_complete() {
+        let path = temp_file("complete", b"hello");
+        let (url, handle) =
+            serve(&["HTTP/1.1 416 Range Not Satisfiable\r\nContent-Range: bytes */5\r\nContent-Length: 0\r\n\r\n"]);
+        // 本地文件完整时服务端返回416，不覆盖文件且继续校验
+        let sha256 = "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824";
+        let result = Downloader::new(&path, true, Some(sha256)).download(Client::new().get(url));
+        let headers = handle.join().unwrap();
+        assert!(result.is_ok());
+        assert!(headers[0].iter().any(|header| header.eq_ignore_ascii_case("range: bytes=5-")));
+        assert_eq!(std::fs::read(&path).unwrap(), b"hello");
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_resume_mismatch() {
+        const FULL: &str = "HTTP/1.1 200 OK\r\nContent-Length: 10\r\nConnection: close\r\n\r\nhelloworld";
+        // 起始位置与本地文件衔接时追加
+        let (ok, content, ranges) = resume(
+            "append",
+            b"hello",
+            &["HTTP/1.1 206 Partial Content\r\nContent-Range: bytes 5-9/10\r\nContent-Length: 5\r\n\r\nworld"],
+        );
+        assert!(ok);
+        assert_eq!(content, b"helloworld");
+        assert_eq!(ranges, [true]);
+        // 服务器返回其他范围时不追加，重新下载
+        let (ok, content, ranges) = resume(
+            "range",
+            b"hello",
+            &[
+                "HTTP/1.1 206 Partial Content\r\nContent-Range: bytes 0-4/10\r\nContent-Length: 5\r\nConnection: close\r\n\r\nhello",
+                FULL,
+            ],
+        );
+        assert!(ok);
+        assert_eq!(content, b"helloworld");
+        assert_eq!(ranges, [true, false]);
+        // 416但总大小与本地文件不同，本地文件被截断或比服务器的大，重新下载
+        for local in [&b"hell"[..], b"helloworld!"] {
+            let (ok, content, ranges) = resume(
+                "size",
+                local,
+                &[
+                    "HTTP/1.1 416 Range Not Satisfiable\r\nContent-Range: bytes */10\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                    FULL,
+                ],
+            );
+            assert!(ok);
+            assert_eq!(content, b"helloworld");
+            assert_eq!(ranges, [true, false]);
+        }
+    }
+
+    #[test]
+    fn test_sha256_mismatch() {
+        let path = temp_file("sha256", b"hello");
+        assert!(Downloader::new(&path, false, None).verify().is_ok());
+        let error = Downloader::new(&path, false, Some("00")).verify().unwrap_err();
+        assert!(error.to_string().contains("SHA256校验失败"));
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,54 @@
+use serde_json::Value;
+
+/// 按路径查询JSON中的值
+///
+/// 路径形如`.data.items[0].id`或`data.items.0.id`，`.`或空路径表示根节点
+pub fn query<'a>(value: &'a Value, path: &str) -> Option<&'a Value> {
+    let mut current = value;
+    for segment in path.trim().trim_start_matches('.').split('.').filter(|s| !s.is_empty()) {
+        // 拆分 items[0][1] 为字段名和下标
+        let (field, indexes) = match segment.find('[') {
+            Some(idx) => segment.split_at(idx),
+            None => (segment, ""),
+        };
+        if !field.is_empty() {
+            current = match current {
+                Value::Object(map) => map.get(field)?,
+                Value::Array(list) => list.get(field.parse::<usize>().ok()?)?,
+                _ => return None,
+            };
+        }
+        for index in indexes.split(['[', ']']).filter(|s| !s.is_empty()) {
+            current = current.as_array()?.get(index.parse::<usize>().ok()?)?;
+        }
+    }
+    Some(current)
+}
+
+/// 将JSON值转换为变量值，字符串不带引号
+pub fn to_plain_string(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_query() {
+        let value = json!({"data": {"token": "abc", "items": [{"id": 1}, {"id": 2}], "matrix": [[1, 2], [3, 4]]}});
+        assert_eq!(query(&value, ".data.token"), Some(&json!("abc")));
+        assert_eq!(query(&value, "data.items[1].id"), Some(&json!(2)));
+        assert_eq!(query(&value, "data.items.0.id"), Some(&json!(1)));
+        assert_eq!(query(&value, "data.matrix[1][0]"), Some(&json!(3)));
+        assert_eq!(query(&value, "."), Some(&value));
+        assert_eq!(query(&value, "data.missing"), None);
+        assert_eq!(query(&value, "data.items[5]"), None);
+        assert_eq!(to_plain_string(&json!("abc")), "abc");
+        assert_eq!(to_plain_string(&json!(12)), "12");
+    }
+}
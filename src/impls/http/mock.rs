@@ -0,0 +1,542 @@
+use crate::error::CliError;
+use crate::impls::handlers::CommandHandler;
+use crate::utils::format::format_bytes;
+use chrono::Local;
+use clap::{Parser, ValueHint};
+use crossterm::style::Stylize;
+use percent_encoding::{NON_ALPHANUMERIC, percent_decode_str, utf8_percent_encode};
+use reqwest::StatusCode;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::io::{self, BufRead, BufReader, ErrorKind, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// 保持连接的空闲超时
+const KEEP_ALIVE_TIMEOUT: Duration = Duration::from_secs(30);
+/// 请求体的最大大小，超过时返回413，避免按客户端声明的长度分配过多内存
+const MAX_BODY_SIZE: usize = 8 * 1024 * 1024;
+
+#[derive(Debug, Parser)]
+pub struct MockHandler {
+    #[arg(
+        default_value = ".",
+        value_hint = ValueHint::AnyPath,
+        help = "静态文件目录，或路由配置文件（JSON/TOML）"
+    )]
+    source: PathBuf,
+
+    #[arg(short, long, default_value_t = 8080, help = "监听端口")]
+    port: u16,
+
+    #[arg(long, default_value = "127.0.0.1", help = "监听地址，0.0.0.0允许其他机器访问")]
+    host: String,
+}
+
+/// 路由配置，按定义顺序匹配第一个满足条件的路由
+#[derive(Debug, Deserialize)]
+struct MockConfig {
+    #[serde(default)]
+    routes: Vec<MockRoute>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MockRoute {
+    // 请求方式，不指定时匹配所有请求方式
+    method: Option<String>,
+    // 请求路径，`:name`匹配任意一段，结尾的`*`匹配剩余部分
+    path: String,
+    #[serde(default = "default_status")]
+    status: u16,
+    #[serde(default)]
+    headers: BTreeMap<String, String>,
+    body: Option<String>,
+    // JSON响应体，会自动设置Content-Type
+    json: Option<serde_json::Value>,
+    // 以文件内容作为响应体，相对路径基于配置文件所在目录
+    file: Option<PathBuf>,
+    // 响应延迟（毫秒）
+    #[serde(default)]
+    delay: u64,
+}
+
+fn default_status() -> u16 {
+    200
+}
+
+/// 服务的内容：静态目录或路由
+enum MockSource {
+    Directory(PathBuf),
+    Routes { config: MockConfig, base: PathBuf },
+}
+
+struct MockRequest {
+    method: String,
+    // 请求目标，包含查询参数
+    target: String,
+    body: Vec<u8>,
+    keep_alive: bool,
+}
+
+struct MockResponse {
+    status: u16,
+    headers: Vec<(String, String)>,
+    body: Vec<u8>,
+}
+
+impl MockConfig {
+    fn load(path: &Path) -> Result<Self, CliError> {
+        let content = std::fs::read_to_string(path)?;
+        let extension = path.extension().and_then(|ext| ext.to_str()).unwrap_or_default();
+        let parse_error = |e: String| CliError::FileSysError(format!("路由配置格式错误 {}: {}", path.display(), e));
+        if extension.eq_ignore_ascii_case("json") {
+            serde_json::from_str(&content).map_err(|e| parse_error(e.to_string()))
+        } else {
+            toml::from_str(&content).map_err(|e| parse_error(e.to_string()))
+        }
+    }
+}
+
+impl MockRoute {
+    fn matches(&self, method: &str, path: &str) -> bool {
+        let method_matched = self
+            .method
+            .as_ref()
+            .is_none_or(|expected| expected.eq_ignore_ascii_case(method));
+        method_matched && match_path(&self.path, path)
+    }
+
+    fn response(&self, base: &Path) -> Result<MockResponse, CliError> {
+        let mut headers = self
+            .headers
+            .iter()
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect::<Vec<(String, String)>>();
+        let (body, content_type) = if let Some(json) = &self.json {
+            (json.to_string().into_bytes(), Some("application/json".to_string()))
+        } else if let Some(file) = &self.file {
+            let path = base.join(file);
+            let body = std::fs::read(&path)
+                .map_err(|e| CliError::FileSysError(format!("无法读取响应文件{}：{}", path.display(), e)))?;
+            (body, mime_guess::from_path(&path).first().map(|mime| mime.to_string()))
+        } else {
+            (self.body.clone().unwrap_or_default().into_bytes(), None)
+        };
+        if let Some(content_type) = content_type
+            && !headers.iter().any(|(key, _)| key.eq_ignore_ascii_case("content-type"))
+        {
+            headers.push(("Content-Type".to_string(), content_type));
+        }
+        Ok(MockResponse {
+            status: self.status,
+            headers,
+            body,
+        })
+    }
+}
+
+/// 按段匹配路径
+fn match_path(pattern: &str, path: &str) -> bool {
+    let mut patterns = pattern.trim_matches('/').split('/');
+    let mut segments = path.trim_matches('/').split('/');
+    loop {
+        match (patterns.next(), segments.next()) {
+            (Some("*"), _) => return true,
+            (Some(pattern), Some(segment)) if pattern.starts_with(':') || pattern == segment => {}
+            (None, None) => return true,
+            _ => return false,
+        }
+    }
+}
+
+impl MockResponse {
+    fn text(status: u16, body: &str) -> Self {
+        MockResponse {
+            status,
+            headers: vec![("Content-Type".to_string(), "text/plain; charset=utf-8".to_string())],
+            body: body.as_bytes().to_vec(),
+        }
+    }
+
+    fn write(&self, stream: &mut TcpStream, head_only: bool, keep_alive: bool) -> std::io::Result<()> {
+        let reason = StatusCode::from_u16(self.status)
+            .ok()
+            .and_then(|status| status.canonical_reason())
+            .unwrap_or("");
+        let mut head = format!("HTTP/1.1 {} {}\r\n", self.status, reason);
+        for (key, value) in &self.headers {
+            head.push_str(&format!("{}: {}\r\n", key, value));
+        }
+        head.push_str(&format!("Content-Length: {}\r\n", self.body.len()));
+        head.push_str(&format!(
+            "Connection: {}\r\nServer: fun-cli\r\n\r\n",
+            if keep_alive { "keep-alive" } else { "close" }
+        ));
+        stream.write_all(head.as_bytes())?;
+        if !head_only {
+            stream.write_all(&self.body)?;
+        }
+        stream.flush()
+    }
+}
+
+impl MockSource {
+    fn handle(&self, request: &MockRequest) -> MockResponse {
+        let path = request.target.split(['?', '#']).next().unwrap_or("/");
+        let result = match self {
+            MockSource::Directory(root) => Ok(serve_file(root, path)),
+            MockSource::Routes { config, base } => {
+                match config.routes.iter().find(|route| route.matches(&request.method, path)) {
+                    Some(route) => {
+                        if route.delay > 0 {
+                            thread::sleep(Duration::from_millis(route.delay));
+                        }
+                        route.response(base)
+                    }
+                    None => Ok(MockResponse::text(404, "no matching route")),
+                }
+            }
+        };
+        result.unwrap_or_else(|e| MockResponse::text(500, &e.to_string()))
+    }
+}
+
+/// 返回目录中的文件，目录返回其中的index.html或文件列表
+fn serve_file(root: &Path, path: &str) -> MockResponse {
+    let decoded = percent_decode_str(path).decode_utf8_lossy();
+    let relative = Path::new(decoded.trim_start_matches('/'));
+    // 只允许普通的路径段，防止通过..访问目录之外的文件
+    if relative
+        .components()
+        .any(|component| !matches!(component, Component::Normal(_)))
+    {
+        return MockResponse::text(403, "forbidden");
+    }
+    let full = root.join(relative);
+    if full.is_dir() {
+        if !path.ends_with('/') {
+            let mut response = MockResponse::text(301, "");
+            response.headers.push(("Location".to_string(), format!("{}/", path)));
+            return response;
+        }
+        if full.join("index.html").is_file() {
+            return serve_file(root, &format!("{}index.html", path));
+        }
+        return list_dir(&full, &decoded).unwrap_or_else(|e| MockResponse::text(500, &e.to_string()));
+    }
+    match std::fs::read(&full) {
+        Ok(body) => MockResponse {
+            status: 200,
+            headers: vec![(
+                "Content-Type".to_string(),
+                mime_guess::from_path(&full).first_or_octet_stream().to_string(),
+            )],
+            body,
+        },
+        Err(e) if e.kind() == ErrorKind::NotFound => MockResponse::text(404, "not found"),
+        Err(e) => MockResponse::text(500, &e.to_string()),
+    }
+}
+
+fn list_dir(dir: &Path, path: &str) -> Result<MockResponse, CliError> {
+    let escape = |s: &str| s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;");
+    let mut names = std::fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if entry.path().is_dir() {
+                format!("{}/", name)
+            } else {
+                name
+            }
+        })
+        .collect::<Vec<String>>();
+    names.sort();
+    let items = names
+        .iter()
+        .map(|name| {
+            let href = utf8_percent_encode(name.trim_end_matches('/'), NON_ALPHANUMERIC).to_string();
+            let href = if name.ends_with('/') {
+                format!("{}/", href)
+            } else {
+                href
+            };
+            format!("<li><a href=\"{}\">{}</a></li>", href, escape(name))
+        })
+        .collect::<String>();
+    let html = format!(
+        "<!DOCTYPE html><html><head><meta charset=\"utf-8\"><title>{0}</title></head><body><h1>{0}</h1><ul>{1}</ul></body></html>",
+        escape(path),
+        items
+    );
+    let mut response = MockResponse::text(200, &html);
+    response.headers = vec![("Content-Type".to_string(), "text/html; charset=utf-8".to_string())];
+    Ok(response)
+}
+
+fn invalid_data(message: String) -> io::Error {
+    io::Error::new(ErrorKind::InvalidData, message)
+}
+
+fn too_large(size: usize) -> io::Error {
+    io::Error::new(
+        ErrorKind::FileTooLarge,
+        format!("请求体过大：{}，最大{}", size, format_bytes(MAX_BODY_SIZE as u64)),
+    )
+}
+
+/// 读取一个请求，连接已关闭时返回`None`
+fn read_request(reader: &mut BufReader<TcpStream>) -> io::Result<Option<MockRequest>> {
+    let mut line = String::new();
+    if reader.read_line(&mut line)? == 0 {
+        return Ok(None);
+    }
+    let bad_request = || invalid_data(format!("无效的请求行：{}", line.trim()));
+    let mut parts = line.split_whitespace();
+    let (method, target, version) = match (parts.next(), parts.next(), parts.next()) {
+        (Some(method), Some(target), Some(version)) => (method.to_string(), target.to_string(), version.to_string()),
+        _ => return Err(bad_request()),
+    };
+    let mut headers = Vec::new();
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some((key, value)) = line.split_once(':') {
+            headers.push((key.trim().to_string(), value.trim().to_string()));
+        }
+    }
+    let header = |name: &str| {
+        headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.to_ascii_lowercase())
+    };
+    let body = if header("transfer-encoding").is_some_and(|value| value.contains("chunked")) {
+        read_chunked(reader)?
+    } else {
+        let length = header("content-length").and_then(|value| value.parse().ok()).unwrap_or(0);
+        if length > MAX_BODY_SIZE {
+            return Err(too_large(length));
+        }
+        let mut body = vec![0; length];
+        reader.read_exact(&mut body)?;
+        body
+    };
+    // HTTP/1.1默认保持连接，HTTP/1.0需要显式声明
+    let keep_alive = match header("connection").as_deref() {
+        Some("close") => false,
+        Some("keep-alive") => true,
+        _ => version == "HTTP/1.1",
+    };
+    Ok(Some(MockRequest {
+        method,
+        target,
+        body,
+        keep_alive,
+    }))
+}
+
+fn read_chunked(reader: &mut BufReader<TcpStream>) -> io::Result<Vec<u8>> {
+    let mut body = Vec::new();
+    loop {
+        let mut line = String::new();
+        reader.read_line(&mut line)?;
+        let size = line.trim().split(';').next().unwrap_or_default();
+        let size =
+            usize::from_str_radix(size, 16).map_err(|_| invalid_data(format!("无效的分块大小：{}", line.trim())))?;
+        if size == 0 {
+            // 忽略末尾的header，直到空行
+            loop {
+                line.clear();
+                if reader.read_line(&mut line)? == 0 || line.trim().is_empty() {
+                    return Ok(body);
+                }
+            }
+        }
+        let start = body.len();
+        if size > MAX_BODY_SIZE - start {
+            return Err(too_large(start.saturating_add(size)));
+        }
+        body.resize(start + size, 0);
+        reader.read_exact(&mut body[start..])?;
+        // 分块数据后的CRLF
+        reader.read_line(&mut line)?;
+    }
+}
+
+fn log_request(request: &MockRequest, response: &MockResponse, elapsed: Duration) {
+    let status = response.status.to_string();
+    let status = match response.status {
+        200..=299 => status.green(),
+        300..=399 => status.cyan(),
+        400..=499 => status.yellow(),
+        _ => status.red(),
+    };
+    let request_body = if request.body.is_empty() {
+        String::new()
+    } else {
+        format!(" 请求体{}", format_bytes(request.body.len() as u64))
+    };
+    println!(
+        "{} {} {} {} {}{} {}",
+        format!("[{}]", Local::now().format("%H:%M:%S%.3f")).dark_grey(),
+        request.method.clone().bold(),
+        request.target,
+        status.bold(),
+        format_bytes(response.body.len() as u64),
+        request_body.dark_grey(),
+        format!("{}ms", elapsed.as_millis()).dark_grey()
+    );
+}
+
+fn log_error(message: &str) {
+    eprintln!(
+        "{} {} {}",
+        format!("[{}]", Local::now().format("%H:%M:%S%.3f")).dark_grey(),
+        "ERROR".red().bold(),
+        message
+    );
+}
+
+/// 处理一个连接上的所有请求
+fn handle_connection(stream: TcpStream, source: &MockSource) -> io::Result<()> {
+    stream.set_read_timeout(Some(KEEP_ALIVE_TIMEOUT))?;
+    let mut writer = stream.try_clone()?;
+    let mut reader = BufReader::new(stream);
+    loop {
+        let request = match read_request(&mut reader) {
+            Ok(Some(request)) => request,
+            Ok(None) => return Ok(()),
+            // 请求体未读取，无法继续复用连接
+            Err(e) if matches!(e.kind(), ErrorKind::InvalidData | ErrorKind::FileTooLarge) => {
+                let status = if e.kind() == ErrorKind::FileTooLarge { 413 } else { 400 };
+                log_error(&e.to_string());
+                MockResponse::text(status, &e.to_string()).write(&mut writer, false, false)?;
+                return Ok(());
+            }
+            // 空闲超时或客户端断开
+            Err(_) => return Ok(()),
+        };
+        let start = Instant::now();
+        let response = source.handle(&request);
+        response.write(&mut writer, request.method == "HEAD", request.keep_alive)?;
+        log_request(&request, &response, start.elapsed());
+        if !request.keep_alive {
+            return Ok(());
+        }
+    }
+}
+
+impl CommandHandler for MockHandler {
+    fn run(&self) -> Result<(), CliError> {
+        let source = if self.source.is_dir() {
+            MockSource::Directory(self.source.clone())
+        } else {
+            let config = MockConfig::load(&self.source)?;
+            let base = self.source.parent().map(Path::to_path_buf).unwrap_or_default();
+            MockSource::Routes { config, base }
+        };
+        let listener = TcpListener::bind((self.host.as_str(), self.port))?;
+        let address = format!("http://{}:{}", self.host, self.port);
+        match &source {
+            MockSource::Directory(root) => println!("📂 静态目录 {} 已在 {} 启动", root.display(), address),
+            MockSource::Routes { config, .. } => {
+                println!("🧪 模拟服务已在 {} 启动，共{}个路由：", address, config.routes.len());
+                for route in &config.routes {
+                    println!(
+                        "  {:<7} {} -> {}",
+                        route.method.as_deref().unwrap_or("*").to_ascii_uppercase(),
+                        route.path,
+                        route.status
+                    );
+                }
+            }
+        }
+        println!("按Ctrl+C停止");
+        let source = Arc::new(source);
+        for stream in listener.incoming() {
+            let stream = match stream {
+                Ok(stream) => stream,
+                Err(e) => {
+                    log_error(&format!("连接失败：{}", e));
+                    continue;
+                }
+            };
+            let source = source.clone();
+            thread::spawn(move || {
+                if let Err(e) = handle_connection(stream, &source) {
+                    log_error(&format!("请求处理失败：{}", e));
+                }
+            });
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_match_routes() {
+        let config: MockConfig = toml::from_str(
+            r#"
+[[routes]]
+method = "get"
+path = "/users/:id"
+json = { id = 1, name = "fun" }
+
+[[routes]]
+path = "/static/*"
+status = 201
+body = "ok"
+headers = { "Content-Type" = "text/plain" }
+"#,
+        )
+        .unwrap();
+        let route = |method: &str, path: &str| config.routes.iter().position(|route| route.matches(method, path));
+        assert_eq!(route("GET", "/users/42"), Some(0));
+        assert_eq!(route("POST", "/users/42"), None);
+        assert_eq!(route("GET", "/users/42/orders"), None);
+        assert_eq!(route("DELETE", "/static/a/b.js"), Some(1));
+
+        let response = config.routes[0].response(Path::new(".")).unwrap();
+        assert_eq!(response.body, br#"{"id":1,"name":"fun"}"#);
+        assert_eq!(
+            response.headers,
+            vec![("Content-Type".to_string(), "application/json".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_body_too_large() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let source = MockSource::Directory(PathBuf::from("."));
+        thread::spawn(move || {
+            for stream in listener.incoming().take(2) {
+                handle_connection(stream.unwrap(), &source).unwrap();
+            }
+        });
+        let send = |request: String| {
+            let mut stream = TcpStream::connect(addr).unwrap();
+            stream.write_all(request.as_bytes()).unwrap();
+            let mut response = String::new();
+            stream.read_to_string(&mut response).unwrap();
+            response
+        };
+        let response = send(format!("POST / HTTP/1.1\r\nContent-Length: {}\r\n\r\n", MAX_BODY_SIZE + 1));
+        assert!(response.starts_with("HTTP/1.1 413"), "{}", response);
+        let response = send("POST / HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\nffffffffff\r\n".to_string());
+        assert!(response.starts_with("HTTP/1.1 413"), "{}", response);
+    }
+}
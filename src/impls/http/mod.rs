@@ -0,0 +1,16 @@
+pub mod assertion;
+pub mod bench;
+pub mod body;
+pub mod client;
+pub mod collection;
+pub mod cookie;
+pub mod curl_command;
+pub mod download;
+pub mod history;
+pub mod json_path;
+pub mod mock;
+pub mod session;
+pub mod sse;
+pub mod tui;
+pub mod upload;
+pub mod websocket;
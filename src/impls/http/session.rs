@@ -0,0 +1,116 @@
+use crate::error::CliError;
+use crate::utils::storage::{data_dir, write_private};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// 与请求体相关的请求头，随每次请求变化，不保存到会话
+const FRAMING_HEADERS: [&str; 6] = [
+    "content-type",
+    "content-length",
+    "transfer-encoding",
+    "content-encoding",
+    "host",
+    "connection",
+];
+
+/// 命名会话：在多次`fun curl`调用之间复用Cookie和默认请求头
+///
+/// 保存在`~/.fun-cli/sessions`下，`<name>.json`为请求头，`<name>.cookies`为Netscape格式的Cookie，
+/// 文件只有当前用户可以读写；只保存通过`--session-header`指定的请求头
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Session {
+    #[serde(skip)]
+    name: String,
+    #[serde(default)]
+    headers: Vec<(String, String)>,
+}
+
+impl Session {
+    /// 加载会话，不存在时创建空会话
+    pub fn load(name: &str) -> Result<Self, CliError> {
+        if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+            return Err(CliError::UnknownError(format!("会话名称只能包含字母、数字、-和_：{}", name)));
+        }
+        let path = Self::file_path(name, "json")?;
+        let mut session = if path.exists() {
+            serde_json::from_str::<Session>(&std::fs::read_to_string(&path)?)
+                .map_err(|e| CliError::FileSysError(format!("会话文件格式错误 {}: {}", path.display(), e)))?
+        } else {
+            Session::default()
+        };
+        session.name = name.to_string();
+        Ok(session)
+    }
+
+    fn file_path(name: &str, extension: &str) -> Result<PathBuf, CliError> {
+        Ok(data_dir("sessions")?.join(format!("{}.{}", name, extension)))
+    }
+
+    pub fn cookie_path(&self) -> Result<PathBuf, CliError> {
+        Self::file_path(&self.name, "cookies")
+    }
+
+    /// 合并请求头：本次指定的请求头覆盖会话中的同名请求头，返回合并结果
+    pub fn merge_headers(&self, headers: &[(String, String)]) -> Vec<(String, String)> {
+        let mut merged = self
+            .headers
+            .iter()
+            .filter(|(key, _)| !is_framing_header(key))
+            .filter(|(key, _)| !headers.iter().any(|(k, _)| k.eq_ignore_ascii_case(key)))
+            .cloned()
+            .collect::<Vec<(String, String)>>();
+        merged.extend(headers.iter().cloned());
+        merged
+    }
+
+    /// 将本次请求中`names`指定的请求头保存为会话的默认请求头，不保存与请求体相关的请求头
+    pub fn remember_headers(&mut self, headers: &[(String, String)], names: &[String]) {
+        let remembered = headers
+            .iter()
+            .filter(|(key, _)| !is_framing_header(key))
+            .filter(|(key, _)| names.iter().any(|name| name.eq_ignore_ascii_case(key)))
+            .cloned()
+            .collect::<Vec<_>>();
+        self.headers = self.merge_headers(&remembered);
+    }
+
+    pub fn save(&self) -> Result<(), CliError> {
+        let content = serde_json::to_string_pretty(self).map_err(|e| CliError::UnknownError(e.to_string()))?;
+        write_private(&Self::file_path(&self.name, "json")?, content)
+    }
+}
+
+fn is_framing_header(key: &str) -> bool {
+    FRAMING_HEADERS.iter().any(|header| header.eq_ignore_ascii_case(key))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn header(key: &str, value: &str) -> (String, String) {
+        (key.to_string(), value.to_string())
+    }
+
+    #[test]
+    fn test_remember_headers() {
+        let mut session = Session {
+            name: "test".to_string(),
+            headers: vec![header("X-Team", "a"), header("Content-Type", "text/plain")],
+        };
+        let headers = [
+            header("Authorization", "Bearer t"),
+            header("Content-Type", "application/json"),
+            header("Content-Length", "2"),
+            header("x-team", "b"),
+        ];
+        let names = ["x-team".to_string(), "content-type".to_string()];
+        session.remember_headers(&headers, &names);
+        // 只保存指定的请求头，已保存的框架请求头也被丢弃
+        assert_eq!(session.headers, [header("x-team", "b")]);
+        assert_eq!(
+            session.merge_headers(&[header("Accept", "*/*")]),
+            [header("x-team", "b"), header("Accept", "*/*")]
+        );
+    }
+}
@@ -0,0 +1,111 @@
+use crate::error::CliError;
+use chrono::Local;
+use crossterm::style::Stylize;
+use reqwest::blocking::Response;
+use std::io::{BufRead, BufReader};
+
+/// 一个Server-Sent Events事件
+#[derive(Debug, PartialEq)]
+pub struct SseEvent {
+    pub event: String,
+    pub id: Option<String>,
+    pub data: String,
+}
+
+/// 按行解析SSE流，遇到空行时分发事件
+#[derive(Debug, Default)]
+pub struct SseParser {
+    event: Option<String>,
+    data: Vec<String>,
+    // 最后一个事件ID，规范要求在后续事件中保持
+    last_id: Option<String>,
+}
+
+impl SseParser {
+    /// 输入一行（不含换行符），返回完整的事件
+    pub fn feed(&mut self, line: &str) -> Option<SseEvent> {
+        if line.is_empty() {
+            let event = self.event.take();
+            if self.data.is_empty() {
+                return None;
+            }
+            return Some(SseEvent {
+                event: event.unwrap_or("message".to_string()),
+                id: self.last_id.clone(),
+                data: std::mem::take(&mut self.data).join("\n"),
+            });
+        }
+        // 冒号开头的是注释，常用于保持连接
+        if line.starts_with(':') {
+            return None;
+        }
+        let (field, value) = match line.split_once(':') {
+            Some((field, value)) => (field, value.strip_prefix(' ').unwrap_or(value)),
+            None => (line, ""),
+        };
+        match field {
+            "event" => self.event = Some(value.to_string()),
+            "data" => self.data.push(value.to_string()),
+            "id" if !value.contains('\0') => self.last_id = Some(value.to_string()),
+            _ => {}
+        }
+        None
+    }
+}
+
+/// 持续读取SSE响应并打印收到的事件，直到服务端关闭连接
+pub fn stream_events(resp: Response) -> Result<(), CliError> {
+    println!("📡 SSE事件流（Ctrl+C退出）：");
+    let mut reader = BufReader::new(resp);
+    let mut parser = SseParser::default();
+    let mut line = String::new();
+    let mut count = 0;
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 {
+            break;
+        }
+        if let Some(event) = parser.feed(line.trim_end_matches(['\r', '\n'])) {
+            count += 1;
+            let id = event.id.map(|id| format!(" id={}", id)).unwrap_or_default();
+            println!(
+                "{} {}{}",
+                format!("[{}]", Local::now().format("%H:%M:%S%.3f")).dark_grey(),
+                format!("event={}", event.event).cyan().bold(),
+                id.yellow()
+            );
+            println!("{}", event.data);
+        }
+    }
+    println!("SSE事件流已结束，共收到{}个事件", count);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_events() {
+        let mut parser = SseParser::default();
+        let stream =
+            ": keep-alive\nid: 1\nevent: update\ndata: {\"a\":1}\ndata:line2\n\ndata: plain\n\nretry: 1000\n\n";
+        let events = stream.lines().filter_map(|line| parser.feed(line)).collect::<Vec<SseEvent>>();
+        assert_eq!(
+            events,
+            vec![
+                SseEvent {
+                    event: "update".to_string(),
+                    id: Some("1".to_string()),
+                    data: "{\"a\":1}\nline2".to_string(),
+                },
+                // 未指定事件名时为message，事件ID沿用上一个
+                SseEvent {
+                    event: "message".to_string(),
+                    id: Some("1".to_string()),
+                    data: "plain".to_string(),
+                },
+            ]
+        );
+    }
+}
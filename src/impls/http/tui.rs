@@ -0,0 +1,508 @@
+use crate::error::CliError;
+use crate::impls::curl::{RequestArgs, parse_header, parse_method, parse_url};
+use crate::impls::http::client::ClientArgs;
+use crate::impls::http::cookie::CookieJar;
+use crate::impls::http::history::{self, HistoryEntry};
+use crate::ui::Coordinate;
+use crate::ui::chart::wrap;
+use crate::ui::event::{InputEvent, poll_event};
+use crate::ui::theme::Theme;
+use crate::ui::widget::{List, Panel, TextArea, Widget};
+use crate::utils::format::format_bytes;
+use crossterm::cursor::{Hide, MoveTo, Show};
+use crossterm::event::{KeyCode, KeyModifiers};
+use crossterm::style::{Print, ResetColor, SetBackgroundColor, SetForegroundColor};
+use crossterm::terminal::{Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen, size};
+use crossterm::{execute, queue, terminal};
+use reqwest::blocking::Client;
+use std::cell::RefCell;
+use std::fmt::{Display, Formatter};
+use std::io::{self, Stdout, Write, stdout};
+use std::rc::Rc;
+use std::sync::Arc;
+use std::time::Instant;
+
+const MIN_WIDTH: u16 = 80;
+const MIN_HEIGHT: u16 = 24;
+
+/// 历史列表中的一项，面板较窄，只显示状态码、请求方式和URL
+struct HistoryItem(HistoryEntry);
+
+impl Display for HistoryItem {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let status = self.0.status.map(|s| s.to_string()).unwrap_or("ERR".to_string());
+        write!(f, "{} {} {}", status, self.0.method, self.0.url)
+    }
+}
+
+// 响应查看组件，可在响应头和响应体之间切换并滚动
+struct ResponseView {
+    coordinate: Coordinate,
+    width: u16,
+    height: u16,
+    headers: Vec<String>,
+    body: String,
+    error: Option<String>,
+    show_headers: bool,
+    scroll_offset: usize,
+    focused: bool,
+    theme: Theme,
+}
+
+impl ResponseView {
+    fn new(left_top: Coordinate, right_bottom: Coordinate, theme: Theme) -> Self {
+        Self {
+            width: right_bottom.x - left_top.x,
+            height: right_bottom.y - left_top.y,
+            coordinate: left_top,
+            headers: vec![],
+            body: String::new(),
+            error: None,
+            show_headers: false,
+            scroll_offset: 0,
+            focused: false,
+            theme,
+        }
+    }
+
+    fn set_response(&mut self, headers: Vec<String>, body: String) {
+        self.headers = headers;
+        // JSON响应体格式化后显示
+        self.body = serde_json::from_str::<serde_json::Value>(&body)
+            .ok()
+            .and_then(|json| serde_json::to_string_pretty(&json).ok())
+            .unwrap_or(body);
+        self.error = None;
+        self.scroll_offset = 0;
+    }
+
+    fn set_error(&mut self, error: String) {
+        self.headers.clear();
+        self.body.clear();
+        self.error = Some(error);
+        self.scroll_offset = 0;
+    }
+
+    fn toggle(&mut self) {
+        self.show_headers = !self.show_headers;
+        self.scroll_offset = 0;
+    }
+
+    /// 当前视图的内容，按宽度折行
+    fn lines(&self) -> Vec<String> {
+        let width = self.width.saturating_sub(2).max(1) as usize;
+        let content = match (&self.error, self.show_headers) {
+            (Some(error), _) => error.clone(),
+            (None, true) => self.headers.join("\n"),
+            (None, false) => self.body.clone(),
+        };
+        // 按显示宽度折行，中文等宽字符占两列
+        content.lines().flat_map(|line| wrap(line, width)).collect()
+    }
+
+    fn visible_lines(&self) -> usize {
+        self.height.saturating_sub(1).max(1) as usize
+    }
+
+    fn scroll(&mut self, delta: isize) {
+        let max_offset = self.lines().len().saturating_sub(self.visible_lines());
+        self.scroll_offset = self.scroll_offset.saturating_add_signed(delta).min(max_offset);
+    }
+}
+
+impl Widget for ResponseView {
+    fn coordinate(&self) -> Coordinate {
+        self.coordinate.clone()
+    }
+    fn width(&self) -> u16 {
+        self.width
+    }
+    fn height(&self) -> u16 {
+        self.height
+    }
+
+    fn render(&self, stdout: &mut Stdout) -> io::Result<()> {
+        let color = if self.error.is_some() {
+            self.theme.secondary_color()
+        } else {
+            self.theme.primary_text_color()
+        };
+        queue!(stdout, SetForegroundColor(color))?;
+        let lines = self.lines();
+        for (i, line) in lines.iter().skip(self.scroll_offset).take(self.visible_lines()).enumerate() {
+            queue!(
+                stdout,
+                MoveTo(self.coordinate.x + 1, self.coordinate.y + 1 + i as u16),
+                Print(line)
+            )?;
+        }
+        queue!(stdout, ResetColor)?;
+        Ok(())
+    }
+
+    fn handle_event(&mut self, event: KeyCode) -> bool {
+        let page = self.visible_lines() as isize;
+        match event {
+            KeyCode::Up => self.scroll(-1),
+            KeyCode::Down => self.scroll(1),
+            KeyCode::PageUp => self.scroll(-page),
+            KeyCode::PageDown => self.scroll(page),
+            KeyCode::Home => self.scroll_offset = 0,
+            KeyCode::End => self.scroll(isize::MAX),
+            KeyCode::Char('t') => self.toggle(),
+            _ => return false,
+        }
+        true
+    }
+
+    fn set_focus(&mut self, focused: bool) {
+        self.focused = focused;
+    }
+}
+
+/// 交互式HTTP客户端：编辑请求、查看响应、从历史中重新加载请求
+pub struct TuiClient {
+    method_panel: Rc<RefCell<Panel<TextArea>>>,
+    url_panel: Rc<RefCell<Panel<TextArea>>>,
+    headers_panel: Rc<RefCell<Panel<TextArea>>>,
+    body_panel: Rc<RefCell<Panel<TextArea>>>,
+    response_panel: Rc<RefCell<Panel<ResponseView>>>,
+    history_panel: Rc<RefCell<Panel<List<HistoryItem>>>>,
+    widgets: Vec<Rc<RefCell<dyn Widget>>>,
+    focus_idx: usize,
+    focus_mode: bool,
+    height: u16,
+    theme: Theme,
+}
+
+impl TuiClient {
+    fn new(width: u16, height: u16, theme: Theme) -> Self {
+        // 左侧历史列表，右侧自上而下：请求方式和URL、请求头、请求体、响应
+        let history_w = (width / 4).max(24);
+        let left = history_w + 2;
+        let method_right = left + 10;
+        // 请求头、请求体面板各占剩余高度的1/5，其余留给响应
+        let pane_h = ((height - 5) / 5).max(3);
+        let headers_top = 5;
+        let body_top = headers_top + pane_h + 2;
+        let response_top = body_top + pane_h + 2;
+
+        let text_panel = |title: &str, lt: Coordinate, rb: Coordinate, multiline: bool| {
+            Rc::new(RefCell::new(Panel::new(
+                title,
+                TextArea::new(lt, rb, theme.clone(), multiline),
+                theme.clone(),
+            )))
+        };
+        let method_panel = text_panel("Method", Coordinate::new(left, 1), Coordinate::new(method_right, 3), false);
+        method_panel.borrow_mut().child_mut().set_text("GET");
+        let url_panel = text_panel(
+            "URL",
+            Coordinate::new(method_right + 2, 1),
+            Coordinate::new(width - 1, 3),
+            false,
+        );
+        url_panel.borrow_mut().child_mut().set_text("https://");
+        let headers_panel = text_panel(
+            "Headers (Name: Value)",
+            Coordinate::new(left, headers_top),
+            Coordinate::new(width - 1, body_top - 2),
+            true,
+        );
+        let body_panel = text_panel(
+            "Body",
+            Coordinate::new(left, body_top),
+            Coordinate::new(width - 1, response_top - 2),
+            true,
+        );
+        let response_panel = Rc::new(RefCell::new(Panel::new(
+            "Response",
+            ResponseView::new(
+                Coordinate::new(left, response_top),
+                Coordinate::new(width - 1, height - 1),
+                theme.clone(),
+            ),
+            theme.clone(),
+        )));
+        let history_panel = Rc::new(RefCell::new(Panel::new(
+            "History",
+            List::new_with_padding(Coordinate::new(1, 1), Coordinate::new(history_w, height - 1), theme.clone(), 2),
+            theme.clone(),
+        )));
+
+        let mut client = TuiClient {
+            method_panel,
+            url_panel,
+            headers_panel,
+            body_panel,
+            response_panel,
+            history_panel,
+            widgets: vec![],
+            focus_idx: 1,
+            focus_mode: false,
+            height,
+            theme,
+        };
+        client.widgets.push(client.method_panel.clone());
+        client.widgets.push(client.url_panel.clone());
+        client.widgets.push(client.headers_panel.clone());
+        client.widgets.push(client.body_panel.clone());
+        client.widgets.push(client.response_panel.clone());
+        client.widgets.push(client.history_panel.clone());
+        client.widgets[client.focus_idx].borrow_mut().set_focus(true);
+        client.reload_history();
+        client
+    }
+
+    /// 从请求历史文件加载历史列表，最新的请求排在最前
+    fn reload_history(&mut self) {
+        let entries = history::load().unwrap_or_default();
+        let items = entries.into_iter().rev().map(HistoryItem).collect();
+        self.history_panel.borrow_mut().child_mut().set_items(items);
+    }
+
+    /// 从编辑面板中组装请求
+    fn request(&self) -> Result<RequestArgs, String> {
+        let method = self.method_panel.borrow().child().text();
+        let method = parse_method(&method)?;
+        let url = parse_url(self.url_panel.borrow().child().text().trim())?;
+        let headers = self
+            .headers_panel
+            .borrow()
+            .child()
+            .text()
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(parse_header)
+            .collect::<Result<Vec<(String, String)>, String>>()?;
+        let body = self.body_panel.borrow().child().text();
+        Ok(RequestArgs {
+            url,
+            method,
+            headers: if headers.is_empty() { None } else { Some(headers) },
+            data: if body.trim().is_empty() { None } else { Some(body) },
+            data_binary: None,
+            data_raw: None,
+            payload: None,
+        })
+    }
+
+    /// 将历史请求加载到编辑面板
+    fn load(&mut self, request: &RequestArgs) {
+        self.method_panel.borrow_mut().child_mut().set_text(&request.method.to_string());
+        self.url_panel.borrow_mut().child_mut().set_text(&request.url);
+        let headers = request
+            .headers
+            .iter()
+            .flatten()
+            .map(|(key, value)| format!("{}: {}", key, value))
+            .collect::<Vec<String>>()
+            .join("\n");
+        self.headers_panel.borrow_mut().child_mut().set_text(&headers);
+        // 历史中的请求体已是读取后的内容，-d、--data-binary、--data-raw都按原文加载
+        let body = request.body().map(|(body, _)| body).unwrap_or_default();
+        self.body_panel.borrow_mut().child_mut().set_text(body);
+    }
+
+    /// 发送请求，结果显示在响应面板并记录到历史
+    fn send(&mut self, client: &Client, client_args: &ClientArgs, stdout: &mut Stdout) -> Result<(), CliError> {
+        let request = match self.request() {
+            Ok(request) => request,
+            Err(e) => {
+                let mut panel = self.response_panel.borrow_mut();
+                panel.set_title("Response");
+                panel.child_mut().set_error(e);
+                return Ok(());
+            }
+        };
+        self.response_panel.borrow_mut().set_title("Sending...");
+        self.render(stdout)?;
+
+        let start = Instant::now();
+        let result = request.build(client).and_then(|req| {
+            let resp = req.send()?;
+            client_args.check_version(resp.version())?;
+            let status = resp.status();
+            let headers = resp
+                .headers()
+                .iter()
+                .map(|(key, value)| format!("{}: {}", key, value.to_str().unwrap_or_default()))
+                .collect::<Vec<String>>();
+            Ok((status, headers, resp.text()?))
+        });
+        let elapsed = start.elapsed();
+        let mut panel = self.response_panel.borrow_mut();
+        match result {
+            Ok((status, headers, body)) => {
+                panel.set_title(&format!(
+                    "Response {} · {}ms · {}",
+                    status,
+                    elapsed.as_millis(),
+                    format_bytes(body.len() as u64)
+                ));
+                panel.child_mut().set_response(headers, body);
+                history::record(&request, Some(status.as_u16()), None, elapsed);
+            }
+            Err(e) => {
+                let error = e.to_string();
+                panel.set_title("Response");
+                panel.child_mut().set_error(error.clone());
+                history::record(&request, None, Some(error), elapsed);
+            }
+        }
+        drop(panel);
+        self.reload_history();
+        Ok(())
+    }
+
+    fn render(&self, stdout: &mut Stdout) -> Result<(), CliError> {
+        execute!(stdout, Clear(ClearType::All))?;
+        for widget in self.widgets.iter() {
+            widget.borrow().render(stdout)?;
+        }
+        let view = if self.response_panel.borrow().child().show_headers {
+            "headers"
+        } else {
+            "body"
+        };
+        let help = if self.focus_mode {
+            "Esc:退出编辑  F5:发送  (URL中)Enter:发送  (历史中)Enter:加载  (响应中)t:切换"
+        } else {
+            "↑↓/Tab:切换面板  Enter:编辑  s/F5:发送  t:切换响应头/体  q:退出"
+        };
+        queue!(
+            stdout,
+            MoveTo(1, self.height),
+            SetForegroundColor(self.theme.secondary_color()),
+            Print(format!("{}  [{}]", help, view)),
+            ResetColor
+        )?;
+        // 编辑时在文本框中显示光标
+        let editor = match self.focus_idx {
+            0 => Some(&self.method_panel),
+            1 => Some(&self.url_panel),
+            2 => Some(&self.headers_panel),
+            3 => Some(&self.body_panel),
+            _ => None,
+        };
+        match editor {
+            Some(editor) if self.focus_mode => {
+                let cursor = editor.borrow().child().cursor_position();
+                queue!(stdout, MoveTo(cursor.x, cursor.y), Show)?;
+            }
+            _ => queue!(stdout, Hide)?,
+        }
+        stdout.flush()?;
+        Ok(())
+    }
+
+    /// 切换面板选中
+    fn next_focus(&mut self, key_code: KeyCode) {
+        self.widgets[self.focus_idx].borrow_mut().set_focus(false);
+        if key_code == KeyCode::Up || key_code == KeyCode::BackTab {
+            self.focus_idx = if self.focus_idx == 0 {
+                self.widgets.len() - 1
+            } else {
+                self.focus_idx - 1
+            };
+        } else {
+            self.focus_idx = (self.focus_idx + 1) % self.widgets.len();
+        }
+        self.widgets[self.focus_idx].borrow_mut().set_focus(true);
+    }
+
+    /// 处理按键，返回false表示退出
+    fn handle_event(
+        &mut self,
+        key_code: KeyCode,
+        client: &Client,
+        client_args: &ClientArgs,
+        stdout: &mut Stdout,
+    ) -> Result<bool, CliError> {
+        if key_code == KeyCode::F(5) {
+            self.send(client, client_args, stdout)?;
+            return Ok(true);
+        }
+        if !self.focus_mode {
+            match key_code {
+                KeyCode::Char('q') => return Ok(false),
+                KeyCode::Char('s') => self.send(client, client_args, stdout)?,
+                KeyCode::Char('t') => self.response_panel.borrow_mut().child_mut().toggle(),
+                KeyCode::Up | KeyCode::Down | KeyCode::Tab | KeyCode::BackTab => self.next_focus(key_code),
+                KeyCode::Enter => self.focus_mode = true,
+                _ => {}
+            }
+            return Ok(true);
+        }
+        match (key_code, self.focus_idx) {
+            (KeyCode::Esc, _) => self.focus_mode = false,
+            // 单行的请求方式、URL中回车即发送
+            (KeyCode::Enter, 0 | 1) => {
+                self.focus_mode = false;
+                self.send(client, client_args, stdout)?;
+            }
+            (KeyCode::Enter, 5) => {
+                let request = self
+                    .history_panel
+                    .borrow()
+                    .child()
+                    .get_selected()
+                    .map(|item| item.0.to_request());
+                match request {
+                    Some(Ok(request)) => {
+                        self.load(&request);
+                        self.focus_mode = false;
+                    }
+                    Some(Err(e)) => self.response_panel.borrow_mut().child_mut().set_error(e.to_string()),
+                    None => {}
+                }
+            }
+            _ => {
+                self.widgets[self.focus_idx].borrow_mut().handle_event(key_code);
+            }
+        }
+        Ok(true)
+    }
+
+    /// 启动交互式客户端
+    pub fn run(client_args: &ClientArgs, theme: Theme) -> Result<(), CliError> {
+        let (terminal_width, terminal_height) = size()?;
+        if terminal_width < MIN_WIDTH || terminal_height < MIN_HEIGHT {
+            return Err(CliError::UnknownError(format!(
+                "终端尺寸过小，至少需要{}x{}",
+                MIN_WIDTH, MIN_HEIGHT
+            )));
+        }
+        // 同一次交互中的请求共享Cookie
+        let (client, _) = client_args.build(Some(Arc::new(CookieJar::default())))?;
+        let mut tui = TuiClient::new(terminal_width - 2, terminal_height - 2, theme.clone());
+        let mut stdout = stdout();
+
+        terminal::enable_raw_mode()?;
+        execute!(stdout, EnterAlternateScreen, SetBackgroundColor(theme.background_color()))?;
+        let result = (|| -> Result<(), CliError> {
+            tui.render(&mut stdout)?;
+            loop {
+                match poll_event()? {
+                    // 原始模式下Ctrl+C不会产生信号，与q一样退出，编辑文本时也生效
+                    Some(InputEvent::Key(KeyCode::Char('c'), modifiers))
+                        if modifiers.contains(KeyModifiers::CONTROL) =>
+                    {
+                        break;
+                    }
+                    Some(InputEvent::Key(code, _)) => {
+                        if !tui.handle_event(code, &client, client_args, &mut stdout)? {
+                            break;
+                        }
+                        tui.render(&mut stdout)?;
+                    }
+                    _ => {}
+                }
+            }
+            Ok(())
+        })();
+        terminal::disable_raw_mode()?;
+        execute!(stdout, LeaveAlternateScreen, Show)?;
+        result
+    }
+}
@@ -0,0 +1,155 @@
+use crate::error::CliError;
+use reqwest::blocking::Body;
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::PathBuf;
+
+/// 超过该大小的文件以流的方式上传，不整个读入内存
+const STREAM_THRESHOLD: u64 = 1024 * 1024;
+
+/// 请求体的来源：`@file`从文件读取，`@-`从标准输入读取，其余为字面内容
+#[derive(Debug, PartialEq)]
+pub enum Payload {
+    Text(String),
+    File(PathBuf),
+    Stdin,
+}
+
+impl Payload {
+    pub fn parse(data: &str) -> Self {
+        match data.strip_prefix('@') {
+            Some("-") => Payload::Stdin,
+            Some(path) if !path.is_empty() => Payload::File(PathBuf::from(path)),
+            _ => Payload::Text(data.to_string()),
+        }
+    }
+
+    /// 根据文件扩展名推断Content-Type
+    pub fn content_type(&self) -> Option<String> {
+        match self {
+            Payload::File(path) => mime_guess::from_path(path).first().map(|mime| mime.to_string()),
+            _ => None,
+        }
+    }
+
+    /// 读取请求体，只在解析命令行参数时调用一次
+    ///
+    /// 与curl一致，`-d`读取文件或标准输入时去掉换行符，`--data-binary`原样发送。
+    /// `stream`为true时大文件在发送时以流的方式读取，不整个读入内存
+    pub fn read(self, binary: bool, stream: bool) -> Result<RequestBody, CliError> {
+        let body = match self {
+            Payload::Text(text) => RequestBody::Bytes(text.into_bytes()),
+            Payload::File(path) => {
+                let file = open(&path)?;
+                if stream && file.metadata()?.len() > STREAM_THRESHOLD {
+                    RequestBody::Stream {
+                        path,
+                        strip_newlines: !binary,
+                    }
+                } else {
+                    RequestBody::Bytes(read_all(file, binary)?)
+                }
+            }
+            Payload::Stdin => RequestBody::Bytes(read_all(io::stdin(), binary)?),
+        };
+        Ok(body)
+    }
+}
+
+/// 已读取的请求体，构建请求时原样发送，不再解析`@`
+#[derive(Debug, Clone, PartialEq)]
+pub enum RequestBody {
+    Bytes(Vec<u8>),
+    // 大文件每次发送时重新打开，以流的方式上传
+    Stream { path: PathBuf, strip_newlines: bool },
+}
+
+impl RequestBody {
+    pub fn to_body(&self) -> Result<Body, CliError> {
+        let body = match self {
+            RequestBody::Bytes(bytes) => Body::from(bytes.clone()),
+            RequestBody::Stream {
+                path,
+                strip_newlines: true,
+            } => Body::new(StripNewlines(open(path)?)),
+            RequestBody::Stream { path, .. } => {
+                let file = open(path)?;
+                let size = file.metadata()?.len();
+                Body::sized(file, size)
+            }
+        };
+        Ok(body)
+    }
+}
+
+fn open(path: &PathBuf) -> Result<File, CliError> {
+    File::open(path).map_err(|e| CliError::UnknownError(format!("无法读取请求体文件{}：{}", path.display(), e)))
+}
+
+fn read_all(mut reader: impl Read, binary: bool) -> Result<Vec<u8>, CliError> {
+    if !binary {
+        return read_stripped(reader);
+    }
+    let mut bytes = Vec::new();
+    reader.read_to_end(&mut bytes)?;
+    Ok(bytes)
+}
+
+fn read_stripped(reader: impl Read) -> Result<Vec<u8>, CliError> {
+    let mut bytes = Vec::new();
+    StripNewlines(reader).read_to_end(&mut bytes)?;
+    Ok(bytes)
+}
+
+/// 读取时去掉回车和换行符
+struct StripNewlines<R>(R);
+
+impl<R: Read> Read for StripNewlines<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        loop {
+            let n = self.0.read(buf)?;
+            if n == 0 {
+                return Ok(0);
+            }
+            let mut len = 0;
+            for i in 0..n {
+                if buf[i] != b'\r' && buf[i] != b'\n' {
+                    buf[len] = buf[i];
+                    len += 1;
+                }
+            }
+            // 读到的全是换行符时继续读，返回0会被当作读取结束
+            if len > 0 {
+                return Ok(len);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_payload() {
+        assert_eq!(Payload::parse("@-"), Payload::Stdin);
+        assert_eq!(Payload::parse("@data.json"), Payload::File(PathBuf::from("data.json")));
+        assert_eq!(Payload::parse("a=1"), Payload::Text("a=1".to_string()));
+        assert_eq!(Payload::parse("@"), Payload::Text("@".to_string()));
+        assert_eq!(Payload::parse("@data.json").content_type().as_deref(), Some("application/json"));
+        assert_eq!(read_stripped("{\r\n  \"a\": 1\n}\n\n".as_bytes()).unwrap(), b"{  \"a\": 1}");
+        assert_eq!(read_all("a\nb\n".as_bytes(), true).unwrap(), b"a\nb\n");
+    }
+
+    #[test]
+    fn test_read_payload() {
+        let path = std::env::temp_dir().join(format!("fun-cli-{}-payload.txt", std::process::id()));
+        std::fs::write(&path, "a=1\n&b=2\n").unwrap();
+        let body = Payload::File(path.clone()).read(false, true).unwrap();
+        assert_eq!(body, RequestBody::Bytes(b"a=1&b=2".to_vec()));
+        let body = Payload::File(path.clone()).read(true, true).unwrap();
+        assert_eq!(body, RequestBody::Bytes(b"a=1\n&b=2\n".to_vec()));
+        std::fs::remove_file(&path).unwrap();
+        assert!(Payload::File(path).read(false, true).is_err());
+    }
+}
@@ -0,0 +1,281 @@
+use crate::error::CliError;
+use crate::impls::curl::RequestArgs;
+use crate::impls::http::client::{ClientArgs, duration};
+use crate::impls::http::upload::RequestBody;
+use crate::utils::format::format_bytes;
+use chrono::Local;
+use crossterm::style::Stylize;
+use native_tls::{Certificate, Identity, TlsConnector};
+use std::io::{BufRead, BufReader, ErrorKind, IsTerminal, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+use tungstenite::client::IntoClientRequest;
+use tungstenite::handshake::client::{Request, Response};
+use tungstenite::http::{HeaderName, HeaderValue};
+use tungstenite::stream::MaybeTlsStream;
+use tungstenite::{Connector, Message, WebSocket};
+use url::Url;
+
+/// 判断是否为WebSocket地址
+pub fn is_websocket_url(url: &str) -> bool {
+    url.starts_with("ws://") || url.starts_with("wss://")
+}
+
+fn timestamp() -> String {
+    format!("[{}]", Local::now().format("%H:%M:%S%.3f"))
+}
+
+fn ws_error(e: tungstenite::Error) -> CliError {
+    CliError::NetRequestError(format!("WebSocket: {}", e))
+}
+
+/// 设置底层TCP连接的读超时，使接收消息时不会一直阻塞，从而可以穿插发送用户输入
+fn set_read_timeout(socket: &WebSocket<MaybeTlsStream<TcpStream>>, timeout: Duration) -> Result<(), CliError> {
+    match socket.get_ref() {
+        MaybeTlsStream::Plain(stream) => stream.set_read_timeout(Some(timeout))?,
+        MaybeTlsStream::NativeTls(stream) => stream.get_ref().set_read_timeout(Some(timeout))?,
+        _ => {}
+    }
+    Ok(())
+}
+
+/// 建立TCP连接，指定连接超时时逐个尝试解析出的地址
+fn connect_tcp(host: &str, port: u16, client: &ClientArgs) -> Result<TcpStream, CliError> {
+    let Some(secs) = client.connect_timeout else {
+        return Ok(TcpStream::connect((host, port))?);
+    };
+    let timeout = duration(secs)?;
+    let mut last_error = None;
+    for addr in (host, port).to_socket_addrs()? {
+        match TcpStream::connect_timeout(&addr, timeout) {
+            Ok(stream) => return Ok(stream),
+            Err(e) => last_error = Some(e),
+        }
+    }
+    Err(last_error
+        .map(CliError::from)
+        .unwrap_or_else(|| CliError::NetRequestError(format!("WebSocket: 无法解析地址{}:{}", host, port))))
+}
+
+/// 通过HTTP代理的CONNECT方法建立到目标地址的隧道
+fn connect_proxy(proxy: &str, host: &str, port: u16, client: &ClientArgs) -> Result<TcpStream, CliError> {
+    let url = Url::parse(proxy).map_err(|e| CliError::UnknownError(format!("Invalid proxy {}: {}", proxy, e)))?;
+    if url.scheme() != "http" {
+        return Err(CliError::UnknownError(format!("WebSocket只支持http://代理：{}", proxy)));
+    }
+    let proxy_host = url.host_str().unwrap_or_default();
+    let mut stream = connect_tcp(proxy_host, url.port_or_known_default().unwrap_or(80), client)?;
+    write!(stream, "CONNECT {0}:{1} HTTP/1.1\r\nHost: {0}:{1}\r\n\r\n", host, port)?;
+    // 逐字节读取响应头，避免缓冲区吞掉隧道建立后服务端发送的数据
+    let mut reader = BufReader::with_capacity(1, stream.try_clone()?);
+    let mut status = String::new();
+    reader.read_line(&mut status)?;
+    let mut line = String::new();
+    while reader.read_line(&mut line)? > 2 {
+        line.clear();
+    }
+    match status.split_whitespace().nth(1) {
+        Some(code) if code.starts_with('2') => Ok(stream),
+        _ => Err(CliError::NetRequestError(format!("WebSocket: 代理连接失败：{}", status.trim()))),
+    }
+}
+
+/// 按HTTP请求的TLS选项构建连接器：跳过证书校验、信任的CA证书和客户端证书
+fn tls_connector(client: &ClientArgs) -> Result<TlsConnector, CliError> {
+    let tls_error = |e: native_tls::Error| CliError::NetRequestError(format!("WebSocket TLS: {}", e));
+    let mut builder = TlsConnector::builder();
+    if client.insecure {
+        builder.danger_accept_invalid_certs(true).danger_accept_invalid_hostnames(true);
+    }
+    if let Some(cacert) = &client.cacert {
+        let content = std::fs::read(cacert)?;
+        let cert = Certificate::from_pem(&content)
+            .or_else(|_| Certificate::from_der(&content))
+            .map_err(tls_error)?;
+        builder.add_root_certificate(cert);
+    }
+    if let (Some(cert), Some(key)) = (&client.cert, &client.key) {
+        let identity = Identity::from_pkcs8(&std::fs::read(cert)?, &std::fs::read(key)?).map_err(tls_error)?;
+        builder.identity(identity);
+    }
+    builder.build().map_err(tls_error)
+}
+
+/// 建立WebSocket连接，连接超时、代理和TLS选项与HTTP请求一致，总超时只作用于握手
+fn connect(
+    handshake: Request,
+    client: &ClientArgs,
+) -> Result<(WebSocket<MaybeTlsStream<TcpStream>>, Response), CliError> {
+    if client.http2 || client.http2_prior_knowledge {
+        return Err(CliError::UnknownError("WebSocket握手只支持HTTP/1.1".to_string()));
+    }
+    let uri = handshake.uri();
+    let secure = uri.scheme_str() == Some("wss");
+    let host = uri.host().unwrap_or_default().trim_matches(['[', ']']).to_string();
+    let port = uri.port_u16().unwrap_or(if secure { 443 } else { 80 });
+    let stream = match &client.proxy {
+        Some(proxy) => connect_proxy(proxy, &host, port, client)?,
+        None => connect_tcp(&host, port, client)?,
+    };
+    let timeout = client.timeout.map(duration).transpose()?;
+    stream.set_read_timeout(timeout)?;
+    stream.set_write_timeout(timeout)?;
+    let connector = if secure {
+        Connector::NativeTls(tls_connector(client)?)
+    } else {
+        Connector::Plain
+    };
+    let (socket, resp) = tungstenite::client_tls_with_config(handshake, stream, None, Some(connector))
+        .map_err(|e| CliError::NetRequestError(format!("WebSocket: {}", e)))?;
+    Ok((socket, resp))
+}
+
+/// 交互式WebSocket控制台：每行输入作为一条文本消息发送，收到的消息带时间戳打印
+///
+/// 请求头随握手请求发送，请求体作为连接后的第一条消息发送，`--data-binary`作为二进制消息发送。
+/// 标准输入为管道时，输入读完后继续接收消息，直到服务端关闭连接
+pub fn console(request: &RequestArgs, client: &ClientArgs) -> Result<(), CliError> {
+    let mut handshake = request.url.as_str().into_client_request().map_err(ws_error)?;
+    for (key, value) in request.headers.iter().flatten() {
+        let name = HeaderName::from_bytes(key.as_bytes())
+            .map_err(|e| CliError::UnknownError(format!("Invalid header {}: {}", key, e)))?;
+        let value = HeaderValue::from_str(value)
+            .map_err(|e| CliError::UnknownError(format!("Invalid header {}: {}", key, e)))?;
+        handshake.headers_mut().insert(name, value);
+    }
+    let (mut socket, resp) = connect(handshake, client)?;
+    println!("🔌 已连接 {}，状态码：{}", request.url, resp.status());
+    println!("输入消息后回车发送，输入/quit或Ctrl+D断开连接");
+    set_read_timeout(&socket, Duration::from_millis(100))?;
+
+    // 在单独的线程中读取标准输入，主线程负责收发消息
+    let interactive = std::io::stdin().is_terminal();
+    let mut input_open = true;
+    let (tx, rx) = mpsc::channel::<Option<String>>();
+    thread::spawn(move || {
+        for line in std::io::stdin().lock().lines() {
+            match line {
+                Ok(line) => {
+                    if tx.send(Some(line)).is_err() {
+                        return;
+                    }
+                }
+                Err(_) => break,
+            }
+        }
+        let _ = tx.send(None);
+    });
+
+    let send = |socket: &mut WebSocket<MaybeTlsStream<TcpStream>>, text: String| -> Result<(), CliError> {
+        println!("{} {} {}", timestamp().dark_grey(), "→".green().bold(), text);
+        socket.send(Message::text(text)).map_err(ws_error)
+    };
+    // WebSocket不使用流式上传，请求体已整体读入内存
+    match (&request.payload, request.body()) {
+        (Some(RequestBody::Bytes(bytes)), Some((_, binary))) => match std::str::from_utf8(bytes) {
+            Ok(text) if !binary => send(&mut socket, text.to_string())?,
+            _ => {
+                println!(
+                    "{} {} <二进制消息 {}>",
+                    timestamp().dark_grey(),
+                    "→".green().bold(),
+                    format_bytes(bytes.len() as u64)
+                );
+                socket.send(Message::binary(bytes.clone())).map_err(ws_error)?;
+            }
+        },
+        (_, Some((data, _))) => send(&mut socket, data.to_string())?,
+        (_, None) => {}
+    }
+    loop {
+        match rx.try_recv() {
+            Ok(Some(line)) if line.trim() == "/quit" => break,
+            Ok(Some(line)) => send(&mut socket, line)?,
+            // 标准输入已关闭
+            Ok(None) | Err(mpsc::TryRecvError::Disconnected) if input_open => {
+                if interactive {
+                    break;
+                }
+                input_open = false;
+            }
+            Ok(None) | Err(_) => {}
+        }
+        match socket.read() {
+            Ok(Message::Text(text)) => println!("{} {} {}", timestamp().dark_grey(), "←".cyan().bold(), text),
+            Ok(Message::Binary(data)) => println!(
+                "{} {} <二进制消息 {}>",
+                timestamp().dark_grey(),
+                "←".cyan().bold(),
+                format_bytes(data.len() as u64)
+            ),
+            Ok(Message::Close(frame)) => {
+                let reason = frame
+                    .map(|frame| format!("{} {}", u16::from(frame.code), frame.reason))
+                    .unwrap_or_default();
+                println!("{} 🔌 服务端关闭连接 {}", timestamp().dark_grey(), reason);
+                return Ok(());
+            }
+            // Ping、Pong由tungstenite自动处理
+            Ok(_) => {}
+            Err(tungstenite::Error::Io(e)) if matches!(e.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => {}
+            Err(tungstenite::Error::ConnectionClosed) => return Ok(()),
+            Err(e) => return Err(ws_error(e)),
+        }
+    }
+    socket.close(None).map_err(ws_error)?;
+    // 等待服务端确认关闭，出错说明连接已断开，直接退出
+    while socket.read().is_ok() {}
+    println!("{} 🔌 连接已断开", timestamp().dark_grey());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+    use std::net::TcpListener;
+
+    #[derive(Debug, Parser)]
+    struct Cli {
+        #[command(flatten)]
+        client: ClientArgs,
+    }
+
+    #[test]
+    fn test_connect_through_proxy() {
+        // 同一个服务既处理CONNECT，又在隧道建立后完成WebSocket握手并回显消息
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::with_capacity(1, stream.try_clone().unwrap());
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line).unwrap();
+            let mut line = String::new();
+            while reader.read_line(&mut line).unwrap() > 2 {
+                line.clear();
+            }
+            stream.write_all(b"HTTP/1.1 200 Connection established\r\n\r\n").unwrap();
+            let mut socket = tungstenite::accept(stream).unwrap();
+            let message = socket.read().unwrap();
+            socket.send(message).unwrap();
+            request_line
+        });
+        let proxy = format!("http://{}", addr);
+        let client = Cli::try_parse_from(["fun", "--proxy", &proxy, "--connect-timeout", "1"])
+            .unwrap()
+            .client;
+        let handshake = "ws://echo.test:9000/ws".into_client_request().unwrap();
+        let (mut socket, resp) = connect(handshake, &client).unwrap();
+        assert_eq!(resp.status(), 101);
+        socket.send(Message::text("hi")).unwrap();
+        assert_eq!(socket.read().unwrap(), Message::text("hi"));
+        assert_eq!(server.join().unwrap().trim(), "CONNECT echo.test:9000 HTTP/1.1");
+
+        let client = Cli::try_parse_from(["fun", "--http2"]).unwrap().client;
+        let handshake = "ws://127.0.0.1:1/ws".into_client_request().unwrap();
+        assert!(connect(handshake, &client).is_err());
+    }
+}
@@ -7,4 +7,5 @@ pub mod game;
 pub mod os;
 
 pub mod games;
+pub mod http;
 pub mod osystem;
@@ -41,7 +41,10 @@ struct NetCloudMusic {
     #[serde(default)]
     link: String,
 }
-const MUSIC_API: &'static str = "https://api.bakaomg.cn/v1/music/netease/search?keyword={keyword}";
+const MUSIC_API: &'static str = "{host}/v1/music/netease/search?keyword={keyword}";
+const MUSIC_API_HOST: &str = "https://api.bakaomg.cn";
+// 替换API地址的环境变量，可指向`fun curl mock`启动的本地服务
+const MUSIC_API_HOST_ENV: &str = "FUN_CLI_MUSIC_API";
 
 impl MusicHandler {
     pub fn new(name: String, play: bool, loop_play: bool) -> Self {
@@ -50,7 +53,9 @@ impl MusicHandler {
 
     pub fn get_internet_music(&self, client: &Client) -> Result<NetCloudMusic, Box<dyn std::error::Error>> {
         let name = form_urlencoded::byte_serialize((&self.name).as_bytes()).collect::<String>();
-        let response = client.get(MUSIC_API.replace("{keyword}", &name)).send()?.error_for_status()?;
+        let host = std::env::var(MUSIC_API_HOST_ENV).unwrap_or(MUSIC_API_HOST.to_string());
+        let api = MUSIC_API.replace("{host}", &host).replace("{keyword}", &name);
+        let response = client.get(api).send()?.error_for_status()?;
         let res: serde_json::Value = response.json()?;
         if let Some(data) = res["data"].as_object() {
             if let Some(list) = data["list"].as_array() {
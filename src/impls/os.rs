@@ -1,35 +1,50 @@
 use crate::error::CliError;
 use crate::impls::handlers::CommandHandler;
+use crate::impls::osystem::connection::ConnectionWidget;
 use crate::impls::osystem::cpu::CpuWidget;
 use crate::impls::osystem::disk::DiskWidget;
+use crate::impls::osystem::layout::{LayoutConfig, WidgetKind};
 use crate::impls::osystem::memory::MemoryWidget;
+use crate::impls::osystem::network::NetworkWidget;
 use crate::impls::osystem::process::ProcessWidget;
 use crate::ui::Coordinate;
-use crate::ui::event::poll_input;
+use crate::ui::chart;
+use crate::ui::event::{InputEvent, poll_event};
 use crate::ui::theme::Theme;
 use crate::ui::widget::{List, Panel, Widget};
 use crate::utils::consts;
 use clap::{Parser, ValueEnum};
-use crossterm::cursor::{Hide, Show};
-use crossterm::event::KeyCode;
-use crossterm::execute;
-use crossterm::style::SetBackgroundColor;
-use crossterm::terminal::{Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen, size};
+use crossterm::cursor::{Hide, MoveTo, Show};
+use crossterm::event::{KeyCode, KeyModifiers};
+use crossterm::style::{Print, SetBackgroundColor, SetForegroundColor};
+use crossterm::terminal::{
+    Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode, size,
+};
+use crossterm::{execute, queue};
 use std::cell::RefCell;
-use std::collections::HashMap;
 use std::io;
 use std::io::{Write, stdout};
 use std::rc::Rc;
 use std::thread::sleep;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use sysinfo::{Components, System};
 
+/// 定时刷新系统信息并记录历史的间隔
+const REFRESH_INTERVAL: Duration = Duration::from_secs(2);
+
 #[derive(Debug, Parser)]
 pub struct OsHandler {
     #[arg(short, long,value_enum, default_value_t = SizeMode::Large, help = "面板大小/small/middle/large")]
     size: SizeMode,
     #[arg(short, long,value_enum, default_value_t = Theme::Cyberpunk, help = "主题/cyberpunk/blackgold/fire/ocean/aurora")]
     theme: Theme,
+    #[arg(
+        short,
+        long,
+        default_value = "default",
+        help = "布局：预设default/compact/resources/network，或TOML布局文件路径"
+    )]
+    layout: String,
 }
 #[derive(Debug, Clone, ValueEnum)]
 pub enum SizeMode {
@@ -38,139 +53,156 @@ pub enum SizeMode {
     Large,
 }
 
-#[derive(Debug, Clone, Hash, Eq, PartialEq)]
-pub enum LayoutPosition {
-    Top,
-    LeftTop,
-    RightTop,
-    LeftBottom,
-    RightBottom,
-}
-
 //布局面板
 struct LayoutPanel {
-    overview_panel: Rc<RefCell<Panel<List<String>>>>,
-    cpu_panel: Rc<RefCell<Panel<CpuWidget>>>,
-    disk_panel: Rc<RefCell<Panel<DiskWidget>>>,
-    memory_panel: Rc<RefCell<Panel<MemoryWidget>>>,
-    process_panel: Rc<RefCell<Panel<ProcessWidget>>>,
-    widgets: Vec<Rc<RefCell<dyn Widget>>>,
+    // 布局中没有的面板为None
+    overview_panel: Option<Rc<RefCell<Panel<List<String>>>>>,
+    connection_panel: Option<Rc<RefCell<Panel<ConnectionWidget>>>>,
+    cpu_panel: Option<Rc<RefCell<Panel<CpuWidget>>>>,
+    disk_panel: Option<Rc<RefCell<Panel<DiskWidget>>>>,
+    memory_panel: Option<Rc<RefCell<Panel<MemoryWidget>>>>,
+    network_panel: Option<Rc<RefCell<Panel<NetworkWidget>>>>,
+    process_panel: Option<Rc<RefCell<Panel<ProcessWidget>>>>,
+    widgets: Vec<Rc<RefCell<dyn Widget>>>, // 按布局中先行后列的顺序切换焦点
     focus_idx: usize,
     focus_mode: bool,
-    layout: HashMap<LayoutPosition, (Coordinate, Coordinate)>,
-    theme: Theme,
+    config: LayoutConfig,
 }
 impl LayoutPanel {
-    fn calculate_layout(width: u16, height: u16) -> HashMap<LayoutPosition, (Coordinate, Coordinate)> {
-        let half_w = width / 2;
-        let percent_h = height / 3;
-        let mut layout = HashMap::with_capacity(5);
-        layout.insert(
-            LayoutPosition::Top,
-            (Coordinate::new(1, 1), Coordinate::new(width - 1, percent_h - 1)),
-        ); // 顶部区域
-        layout.insert(
-            LayoutPosition::LeftTop,
-            (Coordinate::new(1, percent_h + 1), Coordinate::new(half_w, percent_h * 2)),
-        ); // 左上
-        layout.insert(
-            LayoutPosition::RightTop,
-            (
-                Coordinate::new(half_w + 1, percent_h + 1),
-                Coordinate::new(width - 1, percent_h * 2 - 1),
-            ),
-        ); // 右上
-        layout.insert(
-            LayoutPosition::LeftBottom,
-            (Coordinate::new(1, percent_h * 2 + 1), Coordinate::new(half_w - 1, height - 1)),
-        ); // 左下
-        layout.insert(
-            LayoutPosition::RightBottom,
-            (
-                Coordinate::new(half_w + 1, percent_h * 2 + 1),
-                Coordinate::new(width - 1, height - 1),
-            ),
-        ); // 右下
-        layout
-    }
-    fn new(width: u16, height: u16, sys: &mut System, theme: Theme) -> Self {
+    fn new(width: u16, height: u16, sys: &mut System, theme: Theme, config: LayoutConfig) -> Self {
         // queue!(&mut stdout(),MoveTo(0,height+2),Print(format!("尺寸：{} x {}" ,width,height)) ).unwrap();
-        let layout = Self::calculate_layout(width, height);
-        let top = layout.get(&LayoutPosition::Top).unwrap();
-        let left_top = layout.get(&LayoutPosition::LeftTop).unwrap();
-        let right_top = layout.get(&LayoutPosition::RightTop).unwrap();
-        let left_bottom = layout.get(&LayoutPosition::LeftBottom).unwrap();
-        let right_bottom = layout.get(&LayoutPosition::RightBottom).unwrap();
-
-        let process_panel = Panel::new(
-            "Process",
-            ProcessWidget::new(top.0.clone(), top.1.clone(), theme.clone(), sys),
-            theme.clone(),
-        );
-        let mut overview_list = List::new_with_padding(left_top.0.clone(), left_top.1.clone(), theme.clone(), 2);
-        Self::set_overview_panel_list(&mut overview_list, sys);
-        let overview_panel = Panel::new("INFO", overview_list, theme.clone());
-        let cpu_panel = Panel::new(
-            "CPU",
-            CpuWidget::new(right_top.0.clone(), right_top.1.clone(), theme.clone(), sys),
-            theme.clone(),
-        );
-        let disk_panel = Panel::new(
-            "Disk",
-            DiskWidget::new(left_bottom.0.clone(), left_bottom.1.clone(), theme.clone()),
-            theme.clone(),
-        );
-        let memory_panel = Panel::new(
-            "Memory",
-            MemoryWidget::new(right_bottom.0.clone(), right_bottom.1.clone(), theme.clone(), sys),
-            theme.clone(),
-        );
-
         let mut layout_panel = LayoutPanel {
-            overview_panel: Rc::new(RefCell::new(overview_panel)),
-            cpu_panel: Rc::new(RefCell::new(cpu_panel)),
-            disk_panel: Rc::new(RefCell::new(disk_panel)),
-            memory_panel: Rc::new(RefCell::new(memory_panel)),
-            process_panel: Rc::new(RefCell::new(process_panel)),
+            overview_panel: None,
+            connection_panel: None,
+            cpu_panel: None,
+            disk_panel: None,
+            memory_panel: None,
+            network_panel: None,
+            process_panel: None,
             widgets: vec![],
-            layout,
-            theme,
             focus_idx: 0,
             focus_mode: false,
+            config,
         };
-        layout_panel.widgets.push(layout_panel.process_panel.clone());
-        layout_panel.widgets.push(layout_panel.overview_panel.clone());
-        layout_panel.widgets.push(layout_panel.cpu_panel.clone());
-        layout_panel.widgets.push(layout_panel.disk_panel.clone());
-        layout_panel.widgets.push(layout_panel.memory_panel.clone());
+        for (kind, left_top, right_bottom) in layout_panel.config.calculate(width, height) {
+            match kind {
+                WidgetKind::Process => {
+                    let process_widget = ProcessWidget::new(left_top, right_bottom, theme.clone(), sys);
+                    layout_panel.process_panel =
+                        Some(layout_panel.add(Panel::new("Process", process_widget, theme.clone())));
+                }
+                WidgetKind::Info => {
+                    let right_bottom = Self::list_right_bottom(right_bottom);
+                    let mut overview_list = List::new_with_padding(left_top, right_bottom, theme.clone(), 2);
+                    Self::set_overview_panel_list(&mut overview_list, sys);
+                    layout_panel.overview_panel =
+                        Some(layout_panel.add(Panel::new("INFO", overview_list, theme.clone())));
+                }
+                WidgetKind::Connections => {
+                    let connection_widget = ConnectionWidget::new(left_top, right_bottom, theme.clone(), sys);
+                    layout_panel.connection_panel =
+                        Some(layout_panel.add(Panel::new("Connections", connection_widget, theme.clone())));
+                }
+                WidgetKind::Cpu => {
+                    let cpu_widget = CpuWidget::new(left_top, right_bottom, theme.clone(), sys);
+                    layout_panel.cpu_panel = Some(layout_panel.add(Panel::new("CPU", cpu_widget, theme.clone())));
+                }
+                WidgetKind::Disk => {
+                    let mut disk_widget = DiskWidget::new(left_top, right_bottom, theme.clone());
+                    // 记录读写的初始值，下次刷新时计算速率
+                    disk_widget.update(sys);
+                    layout_panel.disk_panel = Some(layout_panel.add(Panel::new("Disk", disk_widget, theme.clone())));
+                }
+                WidgetKind::Network => {
+                    let network_widget = NetworkWidget::new(left_top, right_bottom, theme.clone());
+                    layout_panel.network_panel =
+                        Some(layout_panel.add(Panel::new("Network", network_widget, theme.clone())));
+                }
+                WidgetKind::Memory => {
+                    let memory_widget = MemoryWidget::new(left_top, right_bottom, theme.clone(), sys);
+                    layout_panel.memory_panel =
+                        Some(layout_panel.add(Panel::new("Memory", memory_widget, theme.clone())));
+                }
+            }
+        }
         layout_panel
     }
 
+    /// List的宽高不包含右下角，补齐一格使边框与其他面板对齐
+    fn list_right_bottom(right_bottom: Coordinate) -> Coordinate {
+        Coordinate::new(right_bottom.x + 1, right_bottom.y + 1)
+    }
+
+    /// 终端尺寸变化后按新尺寸移动所有面板，保留历史、排序、过滤条件和弹出层等状态
+    fn resize(&mut self, width: u16, height: u16) {
+        for (kind, left_top, right_bottom) in self.config.calculate(width, height) {
+            let (panel, right_bottom): (Option<Rc<RefCell<dyn Widget>>>, _) = match kind {
+                WidgetKind::Process => (self.process_panel.clone().map(|panel| panel as _), right_bottom),
+                WidgetKind::Info => (
+                    self.overview_panel.clone().map(|panel| panel as _),
+                    Self::list_right_bottom(right_bottom),
+                ),
+                WidgetKind::Connections => (self.connection_panel.clone().map(|panel| panel as _), right_bottom),
+                WidgetKind::Cpu => (self.cpu_panel.clone().map(|panel| panel as _), right_bottom),
+                WidgetKind::Disk => (self.disk_panel.clone().map(|panel| panel as _), right_bottom),
+                WidgetKind::Network => (self.network_panel.clone().map(|panel| panel as _), right_bottom),
+                WidgetKind::Memory => (self.memory_panel.clone().map(|panel| panel as _), right_bottom),
+            };
+            if let Some(panel) = panel {
+                panel.borrow_mut().set_area(left_top, right_bottom);
+            }
+        }
+    }
+
+    /// 加入焦点切换顺序，返回面板以便按类型访问
+    fn add<T: Widget + 'static>(&mut self, panel: Panel<T>) -> Rc<RefCell<Panel<T>>> {
+        let panel = Rc::new(RefCell::new(panel));
+        self.widgets.push(panel.clone());
+        panel
+    }
+
     // 添加更新系统信息的方法
     fn update_system_info(&mut self, sys: &mut System) {
         sys.refresh_all();
-        let top = self.layout.get(&LayoutPosition::Top).unwrap();
-        let left_top = self.layout.get(&LayoutPosition::LeftTop).unwrap();
-        let right_top = self.layout.get(&LayoutPosition::RightTop).unwrap();
-        let left_bottom = self.layout.get(&LayoutPosition::LeftBottom).unwrap();
-        let right_bottom = self.layout.get(&LayoutPosition::RightBottom).unwrap();
-        // 更新进程面板
-        {
-            let mut panel = self.process_panel.borrow_mut();
-            let new_widget = ProcessWidget::new(top.0.clone(), top.1.clone(), self.theme.clone(), sys);
-            panel.update_widget(new_widget);
+        // 更新进程面板，保留排序、过滤条件和选中的进程
+        if let Some(panel) = &self.process_panel {
+            panel.borrow_mut().child_mut().update(sys);
+        }
+        // 更新连接面板，保留过滤条件和选中的连接
+        if let Some(panel) = &self.connection_panel {
+            panel.borrow_mut().child_mut().update(sys);
+        }
+        // 更新CPU面板，保留使用率历史
+        if let Some(panel) = &self.cpu_panel {
+            panel.borrow_mut().child_mut().update(sys);
         }
-        // 更新CPU面板
-        {
-            let mut panel = self.cpu_panel.borrow_mut();
-            let new_widget = CpuWidget::new(right_top.0.clone(), right_top.1.clone(), self.theme.clone(), sys);
-            panel.update_widget(new_widget);
+        // 更新磁盘面板，保留读写历史
+        if let Some(panel) = &self.disk_panel {
+            panel.borrow_mut().child_mut().update(sys);
         }
-        // 更新内存面板
-        {
-            let mut panel = self.memory_panel.borrow_mut();
-            let new_widget = MemoryWidget::new(right_bottom.0.clone(), right_bottom.1.clone(), self.theme.clone(), sys);
-            panel.update_widget(new_widget);
+        // 更新网络面板，保留收发历史
+        if let Some(panel) = &self.network_panel {
+            panel.borrow_mut().child_mut().update();
+        }
+        // 更新内存面板，保留使用率历史
+        if let Some(panel) = &self.memory_panel {
+            panel.borrow_mut().child_mut().update(sys);
+        }
+    }
+
+    /// 记录一次历史数据，只在定时刷新时调用，按键触发的刷新不记录
+    fn sample_history(&mut self) {
+        if let Some(panel) = &self.cpu_panel {
+            panel.borrow_mut().child_mut().sample();
+        }
+        if let Some(panel) = &self.disk_panel {
+            panel.borrow_mut().child_mut().sample();
+        }
+        if let Some(panel) = &self.network_panel {
+            panel.borrow_mut().child_mut().sample();
+        }
+        if let Some(panel) = &self.memory_panel {
+            panel.borrow_mut().child_mut().sample();
         }
     }
 
@@ -235,9 +267,16 @@ impl LayoutPanel {
             pan.borrow_mut().set_focus(true);
         }
     }
+    /// 选中的面板是否正在输入文本
+    fn is_editing(&self) -> bool {
+        self.widgets[self.focus_idx].borrow().is_editing()
+    }
     /// 处理按键
     /// true：表示需要重建UI组件， false表示仅重新渲染数据即可
     fn handle_event(&mut self, key_code: KeyCode) -> bool {
+        if self.is_editing() {
+            return self.widgets[self.focus_idx].borrow_mut().handle_event(key_code);
+        }
         match key_code {
             KeyCode::Up | KeyCode::Down | KeyCode::Tab => {
                 if !self.focus_mode {
@@ -255,61 +294,180 @@ impl LayoutPanel {
                 self.focus_mode = false;
                 false
             }
-            _ => self.widgets[self.focus_idx].borrow_mut().handle_event(key_code),
+            _ => {
+                let refresh = self.widgets[self.focus_idx].borrow_mut().handle_event(key_code);
+                let jump = self
+                    .connection_panel
+                    .as_ref()
+                    .and_then(|panel| panel.borrow_mut().child_mut().take_jump());
+                if let Some(pid) = jump {
+                    self.jump_to_process(pid);
+                }
+                refresh
+            }
+        }
+    }
+    /// 从连接面板跳转到套接字所属的进程
+    fn jump_to_process(&mut self, pid: u32) {
+        let Some(process_panel) = self.process_panel.clone() else {
+            return;
+        };
+        process_panel.borrow_mut().child_mut().select_pid(pid);
+        let process_panel: Rc<RefCell<dyn Widget>> = process_panel;
+        if let Some(idx) = self.widgets.iter().position(|widget| Rc::ptr_eq(widget, &process_panel)) {
+            self.widgets[self.focus_idx].borrow_mut().set_focus(false);
+            self.focus_idx = idx;
+            self.widgets[idx].borrow_mut().set_focus(true);
         }
     }
 }
+impl OsHandler {
+    /// 按面板大小模式从终端尺寸换算面板尺寸，宽度取偶数、高度取3的倍数
+    fn panel_size(&self, terminal_width: u16, terminal_height: u16) -> (u16, u16) {
+        //terminal_width = terminal_width & !1 // 可以利用 & !1 对1按位取反操作。实现位运算向下取偶操作
+        let (width, height) = match self.size {
+            SizeMode::Small => (terminal_width / 3, terminal_height / 3),
+            SizeMode::Middle => (terminal_width / 2, terminal_height / 2),
+            SizeMode::Large => (terminal_width.saturating_sub(2), terminal_height.saturating_sub(2)),
+        };
+        ((width / 2) * 2, (height / 3) * 3)
+    }
+
+    /// 布局需要的最小终端尺寸，与`panel_size`互逆
+    fn min_terminal_size(&self, config: &LayoutConfig) -> (u16, u16) {
+        let (width, height) = config.min_size();
+        let (width, height) = (width.next_multiple_of(2), height.next_multiple_of(3));
+        match self.size {
+            SizeMode::Small => (width.saturating_mul(3), height.saturating_mul(3)),
+            SizeMode::Middle => (width.saturating_mul(2), height.saturating_mul(2)),
+            SizeMode::Large => (width.saturating_add(2), height.saturating_add(2)),
+        }
+    }
+
+    /// 终端过小时不创建面板，避免组件计算坐标时溢出
+    fn render_too_small(&self, stdout: &mut io::Stdout, terminal: (u16, u16), min: (u16, u16)) -> io::Result<()> {
+        let width = terminal.0 as usize;
+        let lines = [
+            "终端尺寸过小".to_string(),
+            format!("当前 {}x{}，至少需要 {}x{}", terminal.0, terminal.1, min.0, min.1),
+            "请放大窗口，按q退出".to_string(),
+        ];
+        for (i, line) in lines.iter().enumerate().take(terminal.1 as usize) {
+            queue!(
+                stdout,
+                MoveTo(0, i as u16),
+                SetForegroundColor(self.theme.highlight_color()),
+                Print(chart::fit(line, width))
+            )?;
+        }
+        Ok(())
+    }
+}
+
 impl CommandHandler for OsHandler {
     fn run(&self) -> Result<(), CliError> {
         let mut stdout = stdout();
+        // 先加载布局，配置有误时不进入全屏
+        let config = LayoutConfig::load(&self.layout)?;
+        let (min_width, min_height) = self.min_terminal_size(&config);
         let mut sys = System::new_all();
-        // enable_raw_mode()?;
         execute!(stdout, EnterAlternateScreen, SetBackgroundColor(self.theme.background_color()))?;
         execute!(stdout, Clear(ClearType::All), Hide)?;
-        let (terminal_width, terminal_height) = size()?;
-        //terminal_width = terminal_width & !1 // 可以利用 & !1 对1按位取反操作。实现位运算向下取偶操作
-        let (mut width, mut height) = match self.size {
-            SizeMode::Small => (terminal_width / 3, terminal_height / 3),
-            SizeMode::Middle => (terminal_width / 2, terminal_height / 2),
-            SizeMode::Large => (terminal_width - 2, terminal_height - 2),
-        };
-        width = (width / 2) * 2;
-        height = (height / 3) * 3;
-        let mut layout_panel = LayoutPanel::new(width, height, &mut sys, self.theme.clone());
-        let mut last_refresh = 1;
-        loop {
-            layout_panel.render(&mut stdout)?;
-            stdout.flush()?;
-            //接收输入
-            if let Some(code) = poll_input()? {
-                match code {
-                    KeyCode::Char('q') => break,
-                    _ => {
-                        if layout_panel.handle_event(code) {
-                            layout_panel.update_system_info(&mut sys);
-                            last_refresh = 0;
-                        }
+        let mut terminal = size()?;
+        // 原始模式下按键立即送达，不需要回车
+        enable_raw_mode()?;
+        let result = (|| -> Result<(), CliError> {
+            let fits = |(width, height): (u16, u16)| width >= min_width && height >= min_height;
+            let mut layout_panel = if fits(terminal) {
+                let (width, height) = self.panel_size(terminal.0, terminal.1);
+                Some(LayoutPanel::new(width, height, &mut sys, self.theme.clone(), config.clone()))
+            } else {
+                None
+            };
+            let mut last_refresh = Instant::now();
+            loop {
+                match layout_panel.as_mut() {
+                    Some(layout_panel) => layout_panel.render(&mut stdout)?,
+                    None => self.render_too_small(&mut stdout, terminal, (min_width, min_height))?,
+                }
+                stdout.flush()?;
+                //接收输入
+                match poll_event()? {
+                    Some(InputEvent::Resize(width, height)) => {
+                        // 按新尺寸重新计算布局并重建面板
+                        terminal = (width, height);
+                        let (width, height) = self.panel_size(width, height);
+                        layout_panel = match layout_panel.take() {
+                            _ if !fits(terminal) => None,
+                            Some(mut layout_panel) => {
+                                layout_panel.resize(width, height);
+                                Some(layout_panel)
+                            }
+                            None => Some(LayoutPanel::new(width, height, &mut sys, self.theme.clone(), config.clone())),
+                        };
+                    }
+                    // 与q一样，Ctrl+C退出，输入过滤条件时也生效
+                    Some(InputEvent::Key(KeyCode::Char('c'), modifiers))
+                        if modifiers.contains(KeyModifiers::CONTROL) =>
+                    {
+                        break;
                     }
+                    Some(InputEvent::Key(code, _)) => match (code, layout_panel.as_mut()) {
+                        (KeyCode::Char('q'), None) => break,
+                        (KeyCode::Char('q'), Some(layout_panel)) if !layout_panel.is_editing() => break,
+                        (_, Some(layout_panel)) => {
+                            if layout_panel.handle_event(code) {
+                                layout_panel.update_system_info(&mut sys);
+                            }
+                        }
+                        _ => {}
+                    },
+                    None => sleep(Duration::from_millis(100)),
                 }
-            } else {
-                sleep(Duration::from_millis(100));
-                if last_refresh % 20 == 0 {
-                    layout_panel.update_system_info(&mut sys);
+                // 按固定间隔刷新并记录历史，持续按键也不会推迟或加快
+                if last_refresh.elapsed() >= REFRESH_INTERVAL {
+                    if let Some(layout_panel) = layout_panel.as_mut() {
+                        layout_panel.update_system_info(&mut sys);
+                        layout_panel.sample_history();
+                    }
+                    last_refresh = Instant::now();
                 }
+                execute!(stdout, Clear(ClearType::All))?;
             }
-            last_refresh += 1;
-            execute!(stdout, Clear(ClearType::All))?;
-        }
-        // disable_raw_mode()?;
+            Ok(())
+        })();
         //恢复终端
+        disable_raw_mode()?;
         execute!(stdout, LeaveAlternateScreen, Show)?;
-        Ok(())
+        result
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use sysinfo::{Disks, System};
+    use super::*;
+    use sysinfo::Disks;
+
+    #[test]
+    fn test_min_terminal_size() {
+        for size in [SizeMode::Small, SizeMode::Middle, SizeMode::Large] {
+            let handler = OsHandler {
+                size,
+                theme: Theme::Cyberpunk,
+                layout: "default".to_string(),
+            };
+            for layout in ["default", "compact", "resources", "network"] {
+                let config = LayoutConfig::load(layout).unwrap();
+                let min = config.min_size();
+                let (width, height) = handler.min_terminal_size(&config);
+                // 最小终端尺寸下面板足够大，再小一格则不够
+                let (panel_width, panel_height) = handler.panel_size(width, height);
+                assert!(panel_width >= min.0 && panel_height >= min.1, "{:?} {}", handler.size, layout);
+                assert!(handler.panel_size(width - 1, height).0 < min.0, "{:?} {}", handler.size, layout);
+                assert!(handler.panel_size(width, height - 1).1 < min.1, "{:?} {}", handler.size, layout);
+            }
+        }
+    }
 
     #[test]
     fn test_sysinfo() {
@@ -0,0 +1,420 @@
+use crate::ui::Coordinate;
+use crate::ui::chart;
+use crate::ui::theme::Theme;
+use crate::ui::widget::{List, Widget};
+use crossterm::cursor::MoveTo;
+use crossterm::event::KeyCode;
+use crossterm::queue;
+use crossterm::style::{Print, SetForegroundColor};
+use netstat2::{AddressFamilyFlags, ProtocolFlags, ProtocolSocketInfo, TcpState, get_sockets_info};
+use std::fmt::Display;
+use std::io::Stdout;
+use std::net::SocketAddr;
+use sysinfo::{Pid, System};
+
+/// 地址列的最大宽度，IPv6地址过长时截断
+const MAX_ADDRESS_WIDTH: usize = 40;
+
+pub struct ConnectionWidget {
+    coordinate: Coordinate,
+    width: u16,
+    height: u16,
+    theme: Theme,
+    connection_list: List<ConnectionInfo>,
+    connections: Vec<ConnectionInfo>, // 全部连接，未过滤
+    state_filter: StateFilter,
+    filter: String,                 // 按端口、地址、状态或进程名过滤
+    editing: bool,                  // 是否正在输入过滤条件
+    address_widths: (usize, usize), // 本地、远端地址列的宽度
+    jump: Option<u32>,              // 等待跳转的进程
+}
+
+/// 按TCP状态过滤，UDP没有状态，只在不过滤时显示
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum StateFilter {
+    All,
+    Listen,
+    Established,
+}
+
+impl StateFilter {
+    fn next(self) -> Self {
+        match self {
+            StateFilter::All => StateFilter::Listen,
+            StateFilter::Listen => StateFilter::Established,
+            StateFilter::Established => StateFilter::All,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            StateFilter::All => "全部",
+            StateFilter::Listen => "LISTEN",
+            StateFilter::Established => "ESTABLISHED",
+        }
+    }
+
+    fn accepts(self, state: Option<TcpState>) -> bool {
+        match self {
+            StateFilter::All => true,
+            StateFilter::Listen => state == Some(TcpState::Listen),
+            StateFilter::Established => state == Some(TcpState::Established),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ConnectionInfo {
+    protocol: &'static str,
+    local: SocketAddr,
+    remote: Option<SocketAddr>, // UDP没有远端地址
+    state: Option<TcpState>,    // UDP没有状态
+    pid: Option<u32>,
+    process: String,
+    line: String, // 按当前列宽格式化后的内容
+}
+
+impl Display for ConnectionInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.line)
+    }
+}
+
+impl ConnectionInfo {
+    /// 监听中的TCP套接字远端为全零地址，显示为`*:*`
+    fn remote_text(&self) -> String {
+        match self.remote {
+            Some(remote) if !remote.ip().is_unspecified() || remote.port() != 0 => remote.to_string(),
+            _ => "*:*".to_string(),
+        }
+    }
+
+    fn state_text(&self) -> String {
+        self.state.map(|state| state.to_string()).unwrap_or("-".to_string())
+    }
+
+    /// 纯数字按端口精确匹配，否则在地址、状态和进程名中查找
+    fn matches(&self, filter: &str) -> bool {
+        if filter.is_empty() {
+            return true;
+        }
+        if let Ok(port) = filter.parse::<u16>() {
+            return self.local.port() == port || self.remote.is_some_and(|remote| remote.port() == port);
+        }
+        [
+            self.local.to_string(),
+            self.remote_text(),
+            self.state_text(),
+            self.process.clone(),
+        ]
+        .iter()
+        .any(|text| text.to_lowercase().contains(filter))
+    }
+}
+
+impl ConnectionWidget {
+    pub fn new(left_top: Coordinate, right_bottom: Coordinate, theme: Theme, sys: &System) -> Self {
+        let width = (right_bottom.x - left_top.x) + 1;
+        let height = (right_bottom.y - left_top.y) + 1;
+        let (list_left_top, list_right_bottom) = Self::list_area(&left_top, width, height);
+        let mut widget = Self {
+            width,
+            height,
+            theme: theme.clone(),
+            coordinate: left_top,
+            connection_list: List::new(list_left_top, list_right_bottom, theme),
+            connections: vec![],
+            state_filter: StateFilter::All,
+            filter: String::new(),
+            editing: false,
+            address_widths: (0, 0),
+            jump: None,
+        };
+        widget.update(sys);
+        widget
+    }
+
+    /// 列表区域，第一行显示过滤条件，第二行为表头
+    fn list_area(left_top: &Coordinate, width: u16, height: u16) -> (Coordinate, Coordinate) {
+        let (x, y) = (left_top.x + 1, left_top.y + 3);
+        (
+            Coordinate::new(x, y),
+            Coordinate::new(x + width.saturating_sub(2), y + height.saturating_sub(3)),
+        )
+    }
+
+    /// 重新读取所有TCP、UDP套接字，系统信息需已刷新
+    pub fn update(&mut self, sys: &System) {
+        let sockets_info = get_sockets_info(
+            AddressFamilyFlags::IPV4 | AddressFamilyFlags::IPV6,
+            ProtocolFlags::TCP | ProtocolFlags::UDP,
+        )
+        .unwrap_or(vec![]);
+        self.connections = sockets_info
+            .into_iter()
+            .map(|socket_info| {
+                let pid = socket_info.associated_pids.first().copied();
+                let mut process = pid
+                    .and_then(|pid| sys.process(Pid::from_u32(pid)))
+                    .map(|process| format!("{}({})", process.name().to_string_lossy(), process.pid()))
+                    .or(pid.map(|pid| pid.to_string()))
+                    .unwrap_or("-".to_string());
+                // 多个进程共享同一个套接字，如fork出的子进程
+                if socket_info.associated_pids.len() > 1 {
+                    process += &format!(" +{}", socket_info.associated_pids.len() - 1);
+                }
+                let (protocol, local, remote, state) = match socket_info.protocol_socket_info {
+                    ProtocolSocketInfo::Tcp(tcp) => (
+                        "TCP",
+                        SocketAddr::new(tcp.local_addr, tcp.local_port),
+                        Some(SocketAddr::new(tcp.remote_addr, tcp.remote_port)),
+                        Some(tcp.state),
+                    ),
+                    ProtocolSocketInfo::Udp(udp) => {
+                        ("UDP", SocketAddr::new(udp.local_addr, udp.local_port), None, None)
+                    }
+                };
+                ConnectionInfo {
+                    protocol,
+                    local,
+                    remote,
+                    state,
+                    pid,
+                    process,
+                    line: String::new(),
+                }
+            })
+            .collect();
+        self.connections
+            .sort_by_key(|connection| (connection.local.port(), connection.protocol, connection.remote));
+        self.refresh_view();
+    }
+
+    /// 按过滤条件重新生成列表，列宽按实际内容调整，保留选中的连接
+    fn refresh_view(&mut self) {
+        let selected = self
+            .connection_list
+            .get_selected()
+            .map(|connection| (connection.protocol, connection.local, connection.remote));
+        let filter = self.filter.to_lowercase();
+        let mut connection_list = self
+            .connections
+            .iter()
+            .filter(|connection| self.state_filter.accepts(connection.state))
+            .filter(|connection| connection.matches(&filter))
+            .cloned()
+            .collect::<Vec<_>>();
+        let width = |text: String| text.chars().count().min(MAX_ADDRESS_WIDTH) + 2;
+        self.address_widths = connection_list.iter().fold((10, 10), |(local, remote), connection| {
+            (
+                local.max(width(connection.local.to_string())),
+                remote.max(width(connection.remote_text())),
+            )
+        });
+        let (local_width, remote_width) = self.address_widths;
+        for connection in connection_list.iter_mut() {
+            connection.line = format!(
+                "{:<5}{:<local_width$}{:<remote_width$}{:<13}{}",
+                connection.protocol,
+                chart::fit(&connection.local.to_string(), MAX_ADDRESS_WIDTH),
+                chart::fit(&connection.remote_text(), MAX_ADDRESS_WIDTH),
+                connection.state_text(),
+                connection.process
+            );
+        }
+        let idx = selected
+            .and_then(|selected| {
+                connection_list
+                    .iter()
+                    .position(|connection| (connection.protocol, connection.local, connection.remote) == selected)
+            })
+            .unwrap_or(0);
+        self.connection_list.set_items(connection_list);
+        self.connection_list.set_selected(idx);
+    }
+
+    /// 取出等待跳转的进程，由布局面板切换到进程面板并选中
+    pub fn take_jump(&mut self) -> Option<u32> {
+        self.jump.take()
+    }
+
+    /// 处理输入过滤条件时的按键，Enter确认，Esc清除
+    fn handle_filter_event(&mut self, event: KeyCode) {
+        match event {
+            KeyCode::Char(c) => self.filter.push(c),
+            KeyCode::Backspace => {
+                self.filter.pop();
+            }
+            KeyCode::Enter => self.editing = false,
+            KeyCode::Esc => {
+                self.filter.clear();
+                self.editing = false;
+            }
+            KeyCode::Up | KeyCode::Down => {
+                self.connection_list.handle_event(event);
+                return;
+            }
+            _ => return,
+        }
+        self.refresh_view();
+    }
+}
+
+impl Widget for ConnectionWidget {
+    fn coordinate(&self) -> Coordinate {
+        self.coordinate.clone()
+    }
+
+    fn width(&self) -> u16 {
+        self.width
+    }
+
+    fn height(&self) -> u16 {
+        self.height
+    }
+
+    fn render(&self, stdout: &mut Stdout) -> std::io::Result<()> {
+        // 标题占用第一行
+        let (x, y) = (self.coordinate.x + 1, self.coordinate.y + 1);
+        let width = self.width.saturating_sub(2) as usize;
+        if self.height < 4 {
+            return Ok(());
+        }
+        let status = format!(
+            "状态(s): {}  过滤(/): {}{}  {}/{}  g:跳转到进程",
+            self.state_filter.label(),
+            self.filter,
+            if self.editing { "▏" } else { "" },
+            self.connection_list.items().len(),
+            self.connections.len()
+        );
+        queue!(
+            stdout,
+            MoveTo(x, y),
+            SetForegroundColor(self.theme.highlight_color()),
+            Print(chart::fit(&status, width)),
+            SetForegroundColor(self.theme.primary_text_color())
+        )?;
+        // 中文表头按列的起始位置逐个输出，与列表的列宽对齐
+        let (local_width, remote_width) = self.address_widths;
+        let columns = [
+            ("协议", 0),
+            ("本地地址", 5),
+            ("远端地址", 5 + local_width),
+            ("状态", 5 + local_width + remote_width),
+            ("进程", 18 + local_width + remote_width),
+        ];
+        for (label, offset) in columns {
+            if offset + 4 <= width {
+                queue!(stdout, MoveTo(x + offset as u16, y + 1), Print(label))?;
+            }
+        }
+        self.connection_list.render(stdout)
+    }
+
+    fn handle_event(&mut self, event: KeyCode) -> bool {
+        if self.editing {
+            self.handle_filter_event(event);
+            return false;
+        }
+        match event {
+            // 移动选中项只需重新渲染，不刷新系统信息
+            KeyCode::Up | KeyCode::Down => {
+                self.connection_list.handle_event(event);
+                false
+            }
+            KeyCode::Char('s') => {
+                self.state_filter = self.state_filter.next();
+                self.refresh_view();
+                false
+            }
+            KeyCode::Char('/') => {
+                self.editing = true;
+                false
+            }
+            KeyCode::Char('g') => {
+                self.jump = self.connection_list.get_selected().and_then(|connection| connection.pid);
+                false
+            }
+            _ => false,
+        }
+    }
+
+    fn set_focus(&mut self, focused: bool) {
+        self.connection_list.set_focus(focused)
+    }
+
+    fn set_area(&mut self, left_top: Coordinate, right_bottom: Coordinate) {
+        self.width = (right_bottom.x - left_top.x) + 1;
+        self.height = (right_bottom.y - left_top.y) + 1;
+        let (list_left_top, list_right_bottom) = Self::list_area(&left_top, self.width, self.height);
+        self.connection_list.set_area(list_left_top, list_right_bottom);
+        self.coordinate = left_top;
+    }
+
+    fn is_editing(&self) -> bool {
+        self.editing
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn connection(
+        protocol: &'static str,
+        local: &str,
+        remote: Option<&str>,
+        state: Option<TcpState>,
+    ) -> ConnectionInfo {
+        ConnectionInfo {
+            protocol,
+            local: local.parse().unwrap(),
+            remote: remote.map(|remote| remote.parse().unwrap()),
+            state,
+            pid: Some(42),
+            process: "nginx(42)".to_string(),
+            line: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_remote_text() {
+        let listen = connection("TCP", "0.0.0.0:80", Some("0.0.0.0:0"), Some(TcpState::Listen));
+        assert_eq!(listen.remote_text(), "*:*");
+        let listen = connection("TCP", "[::]:80", Some("[::]:0"), Some(TcpState::Listen));
+        assert_eq!(listen.remote_text(), "*:*");
+        let established = connection("TCP", "10.0.0.1:80", Some("10.0.0.2:51000"), Some(TcpState::Established));
+        assert_eq!(established.remote_text(), "10.0.0.2:51000");
+        assert_eq!(connection("UDP", "0.0.0.0:53", None, None).remote_text(), "*:*");
+    }
+
+    #[test]
+    fn test_matches() {
+        let established = connection("TCP", "10.0.0.1:80", Some("10.0.0.2:8080"), Some(TcpState::Established));
+        // 纯数字按本地或远端端口精确匹配，不在地址中查找
+        assert!(established.matches("80"));
+        assert!(established.matches("8080"));
+        assert!(!established.matches("10"));
+        assert!(!established.matches("0"));
+        // 其余按地址、状态和进程名查找，调用方已转为小写
+        assert!(established.matches("10.0.0.2"));
+        assert!(established.matches("established"));
+        assert!(established.matches("nginx"));
+        assert!(!established.matches("listen"));
+        assert!(established.matches(""));
+    }
+
+    #[test]
+    fn test_state_filter() {
+        let udp = connection("UDP", "0.0.0.0:53", None, None);
+        assert!(StateFilter::All.accepts(udp.state));
+        assert!(!StateFilter::Listen.accepts(udp.state));
+        assert!(!StateFilter::Established.accepts(udp.state));
+        assert!(StateFilter::Listen.accepts(Some(TcpState::Listen)));
+        assert!(!StateFilter::Listen.accepts(Some(TcpState::Established)));
+        assert!(StateFilter::Established.accepts(Some(TcpState::Established)));
+        assert!(!StateFilter::Established.accepts(Some(TcpState::TimeWait)));
+        assert_eq!(StateFilter::All.next().next().next(), StateFilter::All);
+    }
+}
@@ -1,4 +1,5 @@
 use crate::ui::Coordinate;
+use crate::ui::chart::{self, History};
 use crate::ui::theme::Theme;
 use crate::ui::widget::Widget;
 use crate::utils::consts;
@@ -6,7 +7,10 @@ use crossterm::cursor::MoveTo;
 use crossterm::queue;
 use crossterm::style::{Print, SetForegroundColor};
 use std::io::Stdout;
-use sysinfo::System;
+use sysinfo::{LoadAvg, System};
+
+/// 每列核心进度条的最小宽度
+const CORE_COLUMN_WIDTH: usize = 24;
 
 pub struct CpuWidget {
     coordinate: Coordinate,
@@ -15,14 +19,17 @@ pub struct CpuWidget {
     theme: Theme,
     cpu_brand: String,          // CPU品牌
     cpu_usage: f32,             // CPU使用率
-    core_count: usize,          // CPU物理核心数
-    frequency: u64,             // CPU频率
+    core_count: usize,          // CPU逻辑核心数
+    frequency: (u64, u64, u64), // 各核心的平均、最低、最高频率
     physical_core_count: usize, // CPU物理核心数
+    core_usages: Vec<f32>,      // 每个核心的使用率
+    load_average: LoadAvg,      // 1/5/15分钟平均负载
+    history: History,           // 总使用率历史
 }
 
 impl CpuWidget {
     pub fn new(left_top: Coordinate, right_bottom: Coordinate, theme: Theme, sys: &mut System) -> Self {
-        Self {
+        let mut widget = Self {
             width: (right_bottom.x - left_top.x) + 1,
             height: (right_bottom.y - left_top.y) + 1,
             coordinate: left_top,
@@ -32,14 +39,71 @@ impl CpuWidget {
                 .first()
                 .map(|c| c.brand().to_string())
                 .unwrap_or(consts::UNKNOWN.to_string()),
-            cpu_usage: sys.global_cpu_usage(),
+            cpu_usage: 0.0,
             core_count: sys.cpus().len(),
             physical_core_count: sys.physical_core_count().unwrap_or(0),
-            frequency: sys.cpus().first().map(|c| c.frequency()).unwrap_or(0),
+            frequency: (0, 0, 0),
+            core_usages: vec![],
+            load_average: LoadAvg::default(),
+            history: History::default(),
+        };
+        widget.update(sys);
+        widget
+    }
+
+    /// 刷新使用率，系统信息需已刷新
+    pub fn update(&mut self, sys: &System) {
+        self.cpu_usage = sys.global_cpu_usage();
+        self.core_usages = sys.cpus().iter().map(|cpu| cpu.cpu_usage()).collect();
+        self.frequency = frequency_range(sys.cpus().iter().map(|cpu| cpu.frequency()));
+        self.load_average = System::load_average();
+    }
+
+    /// 记录一次使用率历史，只在定时刷新时调用，保证历史数据的时间间隔一致
+    pub fn sample(&mut self) {
+        self.history.push(self.cpu_usage as f64);
+    }
+
+    /// 各核心频率一致时只显示一个值，否则显示平均值和范围
+    fn frequency_text(&self) -> String {
+        match self.frequency {
+            (average, min, max) if min == max => format!("{}MHz", average),
+            (average, min, max) => format!("{}MHz ({}-{})", average, min, max),
+        }
+    }
+
+    /// 渲染每个核心的使用率进度条，核心较多时分多列显示
+    fn render_cores(&self, stdout: &mut Stdout, x: u16, y: u16, width: usize, rows: usize) -> std::io::Result<()> {
+        let columns = (width / CORE_COLUMN_WIDTH).clamp(1, self.core_usages.len().max(1));
+        let column_width = width / columns;
+        // 序号3列、百分比7列、列间距1列
+        let bar_width = column_width.saturating_sub(12);
+        for (idx, usage) in self.core_usages.iter().enumerate().take(rows * columns) {
+            let (row, column) = (idx / columns, idx % columns);
+            let ratio = *usage as f64 / 100.0;
+            queue!(
+                stdout,
+                MoveTo(x + (column * column_width) as u16, y + row as u16),
+                SetForegroundColor(self.theme.primary_text_color()),
+                Print(format!("{:>3} ", idx)),
+                SetForegroundColor(self.theme.usage_color(ratio)),
+                Print(chart::gauge(ratio, bar_width)),
+                SetForegroundColor(self.theme.primary_text_color()),
+                Print(format!("{:>6.1}%", usage))
+            )?;
         }
+        Ok(())
     }
 }
 
+/// 计算平均、最低、最高频率，没有核心时均为0
+fn frequency_range(frequencies: impl Iterator<Item = u64>) -> (u64, u64, u64) {
+    let (count, sum, min, max) = frequencies.fold((0, 0, u64::MAX, 0), |(count, sum, min, max), frequency| {
+        (count + 1, sum + frequency, min.min(frequency), max.max(frequency))
+    });
+    if count == 0 { (0, 0, 0) } else { (sum / count, min, max) }
+}
+
 impl Widget for CpuWidget {
     fn coordinate(&self) -> Coordinate {
         self.coordinate.clone()
@@ -53,30 +117,92 @@ impl Widget for CpuWidget {
         self.height
     }
 
+    fn set_area(&mut self, left_top: Coordinate, right_bottom: Coordinate) {
+        self.width = (right_bottom.x - left_top.x) + 1;
+        self.height = (right_bottom.y - left_top.y) + 1;
+        self.coordinate = left_top;
+    }
+
     fn render(&self, stdout: &mut Stdout) -> std::io::Result<()> {
-        let (x, y) = (self.coordinate().x + 2, self.coordinate().y + 2);
+        // 标题占用第一行
+        let (x, mut y) = (self.coordinate.x + 1, self.coordinate.y + 1);
+        let width = self.width.saturating_sub(2) as usize;
+        let mut rows = self.height.saturating_sub(1) as usize;
+        let info = [
+            format!("CPU型号: {}", self.cpu_brand),
+            format!(
+                "使用率: {:.1}%  核心数: {}  物理核心数: {}  频率: {}",
+                self.cpu_usage,
+                self.core_count,
+                self.physical_core_count,
+                self.frequency_text()
+            ),
+            format!(
+                "平均负载: {:.2} {:.2} {:.2} (1/5/15分钟)",
+                self.load_average.one, self.load_average.five, self.load_average.fifteen
+            ),
+        ];
         queue!(stdout, SetForegroundColor(self.theme.primary_text_color()))?;
-        queue!(stdout, MoveTo(x, y), Print(format!("CPU型号:      {:>5}", self.cpu_brand)))?;
-        queue!(
-            stdout,
-            MoveTo(x, y + 1),
-            Print(format!("CPU使用率:     {:>5.2}%", self.cpu_usage))
-        )?;
-        queue!(
-            stdout,
-            MoveTo(x, y + 2),
-            Print(format!("Core核心数:    {:>6}", self.core_count))
-        )?;
-        queue!(
-            stdout,
-            MoveTo(x, y + 3),
-            Print(format!("Core物理核心数: {:>5}", self.core_count))
-        )?;
+        for line in info.iter().take(rows) {
+            queue!(stdout, MoveTo(x, y), Print(chart::fit(line, width)))?;
+            y += 1;
+            rows -= 1;
+        }
+
+        // 剩余空间优先显示全部核心，历史图至少需要3行（说明1行，图2行）
+        let core_rows = self.core_usages.len().div_ceil((width / CORE_COLUMN_WIDTH).max(1));
+        let graph_rows = if rows >= core_rows + 3 {
+            rows - core_rows
+        } else if rows >= 6 {
+            3
+        } else {
+            0
+        };
+        let core_rows = core_rows.min(rows - graph_rows);
+        self.render_cores(stdout, x, y, width, core_rows)?;
+        y += core_rows as u16;
+        let values = self.history.values();
+        if graph_rows == 0 {
+            // 空间不足时用一行迷你折线图代替
+            if rows > core_rows {
+                queue!(
+                    stdout,
+                    MoveTo(x, y),
+                    SetForegroundColor(self.theme.secondary_color()),
+                    Print(chart::sparkline(&values, 100.0, width))
+                )?;
+            }
+            return Ok(());
+        }
         queue!(
             stdout,
-            MoveTo(x, y + 4),
-            Print(format!("Core频率:      {:>5} MHz", self.frequency))
+            MoveTo(x, y),
+            SetForegroundColor(self.theme.primary_text_color()),
+            Print(chart::fit(
+                &format!(
+                    "使用率历史 (近{}秒, 峰值{:.1}%)",
+                    self.history.span(width * 2).as_secs(),
+                    self.history.max()
+                ),
+                width
+            )),
+            SetForegroundColor(self.theme.secondary_color())
         )?;
+        for (i, line) in chart::braille_graph(&values, 100.0, width, graph_rows - 1).iter().enumerate() {
+            queue!(stdout, MoveTo(x, y + 1 + i as u16), Print(line))?;
+        }
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frequency_range() {
+        assert_eq!(frequency_range([1200, 3600, 2400].into_iter()), (2400, 1200, 3600));
+        assert_eq!(frequency_range([2000, 2000].into_iter()), (2000, 2000, 2000));
+        assert_eq!(frequency_range(std::iter::empty()), (0, 0, 0));
+    }
+}
@@ -1,12 +1,21 @@
 use crate::ui::Coordinate;
+use crate::ui::chart::{self, History};
 use crate::ui::theme::Theme;
 use crate::ui::widget::Widget;
 use crate::utils::consts;
+use crate::utils::format::format_bytes;
 use crossterm::cursor::MoveTo;
+use crossterm::event::KeyCode;
 use crossterm::queue;
 use crossterm::style::{Print, SetForegroundColor};
+use std::collections::HashMap;
 use std::io::Stdout;
-use sysinfo::Disks;
+use std::path::Path;
+use std::time::Instant;
+use sysinfo::{Disks, System};
+
+/// 每个磁盘占用的行数
+const DISK_ROWS: usize = 2;
 
 pub struct DiskWidget {
     coordinate: Coordinate,
@@ -14,14 +23,30 @@ pub struct DiskWidget {
     height: u16,
     theme: Theme,
     disks: Vec<DiskInfo>,
+    offset: usize,                      // 滚动位置，第一个显示的磁盘
+    io: Option<IoCounters>,             // 上一次的读写累计值
+    rates: HashMap<String, (f64, f64)>, // 每个设备的读写速率，字节/秒
+    read_rate: f64,
+    write_rate: f64,
+    read_history: History,
+    write_history: History,
 }
 struct DiskInfo {
     name: String,        // 磁盘名称
+    mount_point: String, // 挂载点
     file_system: String, // 文件系统
     kind: String,        // 磁盘类型
     total: u64,
     available: u64,
 }
+
+/// 读写字节数的累计值
+struct IoCounters {
+    time: Instant,
+    total: (u64, u64),
+    devices: HashMap<String, (u64, u64)>,
+}
+
 impl DiskWidget {
     pub fn new(left_top: Coordinate, right_bottom: Coordinate, theme: Theme) -> Self {
         Self {
@@ -29,20 +54,155 @@ impl DiskWidget {
             height: (right_bottom.y - left_top.y) + 1,
             coordinate: left_top,
             theme,
-            disks: Disks::new_with_refreshed_list()
-                .list()
+            disks: read_disks(),
+            offset: 0,
+            io: None,
+            rates: HashMap::new(),
+            read_rate: 0.0,
+            write_rate: 0.0,
+            read_history: History::default(),
+            write_history: History::default(),
+        }
+    }
+
+    /// 刷新磁盘列表并根据两次读写累计值的差计算速率，系统信息需已刷新
+    pub fn update(&mut self, sys: &System) {
+        self.disks = read_disks();
+        self.offset = self.offset.min(self.max_offset());
+        let io = read_io(sys);
+        if let Some(last) = &self.io {
+            let seconds = io.time.duration_since(last.time).as_secs_f64().max(f64::EPSILON);
+            let rate = |now: (u64, u64), last: (u64, u64)| {
+                (
+                    now.0.saturating_sub(last.0) as f64 / seconds,
+                    now.1.saturating_sub(last.1) as f64 / seconds,
+                )
+            };
+            (self.read_rate, self.write_rate) = rate(io.total, last.total);
+            self.rates = io
+                .devices
                 .iter()
-                .map(|disk| DiskInfo {
-                    name: disk.name().to_str().unwrap_or(consts::UNKNOWN).to_string(),
-                    file_system: disk.file_system().to_str().unwrap_or(consts::UNKNOWN).to_string(),
-                    kind: disk.kind().to_string(),
-                    total: disk.total_space(),
-                    available: disk.available_space(),
-                })
-                .collect::<Vec<_>>(),
+                .filter_map(|(device, now)| Some((device.clone(), rate(*now, *last.devices.get(device)?))))
+                .collect();
         }
+        self.io = Some(io);
+    }
+
+    /// 记录一次读写速率历史，只在定时刷新时调用
+    pub fn sample(&mut self) {
+        self.read_history.push(self.read_rate);
+        self.write_history.push(self.write_rate);
+    }
+
+    /// 显示读写历史需要的行数，剩余空间至少能显示一个磁盘时才显示
+    fn history_rows(&self) -> usize {
+        if self.height.saturating_sub(2) as usize >= 2 + DISK_ROWS {
+            2
+        } else {
+            0
+        }
+    }
+
+    /// 可以同时显示的磁盘个数
+    fn visible_count(&self) -> usize {
+        (self.height.saturating_sub(2) as usize).saturating_sub(self.history_rows()) / DISK_ROWS
+    }
+
+    fn max_offset(&self) -> usize {
+        self.disks.len().saturating_sub(self.visible_count())
+    }
+
+    fn render_disk(&self, stdout: &mut Stdout, x: u16, y: u16, width: usize, disk: &DiskInfo) -> std::io::Result<()> {
+        let used = disk.total.saturating_sub(disk.available);
+        let ratio = used as f64 / disk.total.max(1) as f64;
+        // sysinfo的磁盘名称为设备路径，如/dev/sda1，/proc/diskstats中只有最后一段
+        let device = Path::new(&disk.name)
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or(&disk.name);
+        let mut title = format!("{}  {} {} {}", disk.mount_point, disk.name, disk.file_system, disk.kind);
+        if let Some((read, write)) = self.rates.get(device) {
+            title += &format!("  读 {}/s 写 {}/s", format_bytes(*read as u64), format_bytes(*write as u64));
+        }
+        let size = format!("{:>6.1}% {}/{}", ratio * 100.0, format_bytes(used), format_bytes(disk.total));
+        queue!(
+            stdout,
+            MoveTo(x, y),
+            SetForegroundColor(self.theme.primary_text_color()),
+            Print(chart::fit(&title, width)),
+            MoveTo(x, y + 1),
+            Print("  "),
+            SetForegroundColor(self.theme.usage_color(ratio)),
+            Print(chart::gauge(ratio, width.saturating_sub(size.len() + 3))),
+            SetForegroundColor(self.theme.primary_text_color()),
+            Print(format!(" {}", size))
+        )?;
+        Ok(())
+    }
+}
+
+fn read_disks() -> Vec<DiskInfo> {
+    Disks::new_with_refreshed_list()
+        .list()
+        .iter()
+        .map(|disk| DiskInfo {
+            name: disk.name().to_str().unwrap_or(consts::UNKNOWN).to_string(),
+            mount_point: disk.mount_point().display().to_string(),
+            file_system: disk.file_system().to_str().unwrap_or(consts::UNKNOWN).to_string(),
+            kind: disk.kind().to_string(),
+            total: disk.total_space(),
+            available: disk.available_space(),
+        })
+        .collect::<Vec<_>>()
+}
+
+/// 从`/proc/diskstats`读取每个设备的读写字节数，总量只统计物理磁盘，避免分区和设备映射重复计算
+#[cfg(target_os = "linux")]
+fn read_io(_sys: &System) -> IoCounters {
+    let devices = std::fs::read_to_string("/proc/diskstats")
+        .map(|content| parse_diskstats(&content))
+        .unwrap_or_default();
+    let total = devices
+        .iter()
+        .filter(|(device, _)| {
+            !["loop", "ram", "zram", "dm-"].iter().any(|prefix| device.starts_with(prefix))
+                && Path::new("/sys/block").join(device).exists()
+        })
+        .fold((0, 0), |sum, (_, (read, write))| (sum.0 + read, sum.1 + write));
+    IoCounters {
+        time: Instant::now(),
+        total,
+        devices,
     }
 }
+
+/// 其他平台没有按设备的统计，用所有进程的累计读写字节数近似
+#[cfg(not(target_os = "linux"))]
+fn read_io(sys: &System) -> IoCounters {
+    let total = sys.processes().values().fold((0, 0), |sum, process| {
+        let usage = process.disk_usage();
+        (sum.0 + usage.total_read_bytes, sum.1 + usage.total_written_bytes)
+    });
+    IoCounters {
+        time: Instant::now(),
+        total,
+        devices: HashMap::new(),
+    }
+}
+
+/// 解析`/proc/diskstats`，第6、10列为读、写的扇区数，每个扇区固定512字节
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn parse_diskstats(content: &str) -> HashMap<String, (u64, u64)> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let fields = line.split_whitespace().collect::<Vec<_>>();
+            let sectors = |idx: usize| fields.get(idx)?.parse::<u64>().ok();
+            Some((fields.get(2)?.to_string(), (sectors(5)? * 512, sectors(9)? * 512)))
+        })
+        .collect()
+}
+
 impl Widget for DiskWidget {
     fn coordinate(&self) -> Coordinate {
         self.coordinate.clone()
@@ -56,34 +216,98 @@ impl Widget for DiskWidget {
         self.height
     }
 
+    fn set_area(&mut self, left_top: Coordinate, right_bottom: Coordinate) {
+        self.width = (right_bottom.x - left_top.x) + 1;
+        self.height = (right_bottom.y - left_top.y) + 1;
+        self.coordinate = left_top;
+        self.offset = self.offset.min(self.max_offset());
+    }
+
     fn render(&self, stdout: &mut Stdout) -> std::io::Result<()> {
-        queue!(stdout, SetForegroundColor(self.theme.primary_text_color()))?;
-        let (x, y) = (self.coordinate().x + 2, self.coordinate().y + 2);
-        // for (i, disk) in self.disks.iter().enumerate() {
-        //     if i>=1 {
-        //         break;
-        //     }
-        //
-        //     x = x + i as u16 * 20;
-        // };
-        let disk = self.disks.get(0).unwrap();
-        queue!(stdout, MoveTo(x, y), Print(format!("磁盘名称:     {:>12}", disk.name)))?;
-        queue!(
-            stdout,
-            MoveTo(x, y + 1),
-            Print(format!("文件系统类型:  {:>12}", disk.file_system))
-        )?;
-        queue!(stdout, MoveTo(x, y + 2), Print(format!("磁盘类型:      {:>12}", disk.kind)))?;
-        queue!(
-            stdout,
-            MoveTo(x, y + 3),
-            Print(format!("磁盘总空间:    {:>10}GB", disk.total / consts::SIZE_GB))
-        )?;
+        // 标题占用第一行
+        let (x, mut y) = (self.coordinate.x + 1, self.coordinate.y + 1);
+        let width = self.width.saturating_sub(2) as usize;
+        if self.height < 2 {
+            return Ok(());
+        }
+        let visible = self.visible_count();
+        let mut summary = format!(
+            "读取 {}/s  写入 {}/s",
+            format_bytes(self.read_rate as u64),
+            format_bytes(self.write_rate as u64)
+        );
+        if self.disks.len() > visible {
+            summary += &format!(
+                "  磁盘 {}-{}/{} (↑↓滚动)",
+                self.offset + 1,
+                (self.offset + visible).min(self.disks.len()),
+                self.disks.len()
+            );
+        }
         queue!(
             stdout,
-            MoveTo(x, y + 4),
-            Print(format!("可用空间:      {:>10}GB", disk.available / consts::SIZE_GB))
+            MoveTo(x, y),
+            SetForegroundColor(self.theme.primary_text_color()),
+            Print(chart::fit(&summary, width))
         )?;
+        y += 1;
+
+        if self.history_rows() > 0 {
+            // 读写共用同一个比例，便于对比
+            let max = self.read_history.max().max(self.write_history.max());
+            let histories = [("读", &self.read_history), ("写", &self.write_history)];
+            for (label, history) in histories {
+                queue!(
+                    stdout,
+                    MoveTo(x, y),
+                    SetForegroundColor(self.theme.primary_text_color()),
+                    Print(format!("{} ", label)),
+                    SetForegroundColor(self.theme.secondary_color()),
+                    Print(chart::sparkline(&history.values(), max, width.saturating_sub(3)))
+                )?;
+                y += 1;
+            }
+        }
+
+        if self.disks.is_empty() {
+            if visible > 0 {
+                queue!(
+                    stdout,
+                    MoveTo(x, y),
+                    SetForegroundColor(self.theme.primary_text_color()),
+                    Print("未发现磁盘")
+                )?;
+            }
+            return Ok(());
+        }
+        for disk in self.disks.iter().skip(self.offset).take(visible) {
+            self.render_disk(stdout, x, y, width, disk)?;
+            y += DISK_ROWS as u16;
+        }
         Ok(())
     }
+
+    fn handle_event(&mut self, event: KeyCode) -> bool {
+        // 滚动只需重新渲染，不刷新系统信息
+        match event {
+            KeyCode::Up => self.offset = self.offset.saturating_sub(1),
+            KeyCode::Down => self.offset = (self.offset + 1).min(self.max_offset()),
+            _ => {}
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_diskstats() {
+        let stats = parse_diskstats(
+            " 253       0 vda 1000 10 2048 500 300 20 4096 800 0 900 1300 0 0 0 0 0 0\n 253       1 vda1 10 0 8 1 0 0 0 0 0 1 1\n",
+        );
+        assert_eq!(stats["vda"], (2048 * 512, 4096 * 512));
+        assert_eq!(stats["vda1"], (8 * 512, 0));
+    }
 }
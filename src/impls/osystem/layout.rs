@@ -0,0 +1,257 @@
+use crate::error::CliError;
+use crate::ui::Coordinate;
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::path::Path;
+
+/// 面板内容区域的最小宽高，再小时组件无法正常显示
+const MIN_PANEL_WIDTH: u16 = 20;
+const MIN_PANEL_HEIGHT: u16 = 5;
+
+/// 内置布局预设：名称、说明、配置
+const PRESETS: [(&str, &str, &str); 4] = [
+    (
+        "default",
+        "进程列表在上，中间为概览、连接和CPU，底部为磁盘、网络和内存",
+        DEFAULT,
+    ),
+    ("compact", "进程列表占大半屏，底部为CPU、内存和网络", COMPACT),
+    ("resources", "以CPU、内存、磁盘和网络为主，底部为进程列表", RESOURCES),
+    ("network", "以连接和网络为主，底部为进程列表", NETWORK),
+];
+
+const DEFAULT: &str = r#"
+[[rows]]
+columns = [{ widget = "process" }]
+
+[[rows]]
+columns = [
+    { widget = "info", ratio = 2 },
+    { widget = "connections", ratio = 3 },
+    { widget = "cpu", ratio = 3 },
+]
+
+[[rows]]
+columns = [{ widget = "disk" }, { widget = "network" }, { widget = "memory" }]
+"#;
+
+const COMPACT: &str = r#"
+[[rows]]
+ratio = 2
+columns = [{ widget = "process" }]
+
+[[rows]]
+columns = [{ widget = "cpu" }, { widget = "memory" }, { widget = "network" }]
+"#;
+
+const RESOURCES: &str = r#"
+[[rows]]
+columns = [{ widget = "cpu" }, { widget = "memory" }]
+
+[[rows]]
+columns = [{ widget = "disk" }, { widget = "network" }]
+
+[[rows]]
+columns = [{ widget = "process" }]
+"#;
+
+const NETWORK: &str = r#"
+[[rows]]
+ratio = 2
+columns = [{ widget = "connections", ratio = 3 }, { widget = "network", ratio = 2 }]
+
+[[rows]]
+columns = [{ widget = "process" }]
+"#;
+
+/// 面板布局：自上而下的若干行，每行按比例分为若干列，每列放置一个面板
+///
+/// 行高、列宽按`ratio`分配，相邻面板共用边框
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LayoutConfig {
+    rows: Vec<RowConfig>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RowConfig {
+    #[serde(default = "default_ratio")]
+    ratio: u16,
+    columns: Vec<ColumnConfig>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ColumnConfig {
+    widget: WidgetKind,
+    #[serde(default = "default_ratio")]
+    ratio: u16,
+}
+
+fn default_ratio() -> u16 {
+    1
+}
+
+/// 可放置的面板，每种最多出现一次
+#[derive(Debug, Clone, Copy, Hash, Eq, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WidgetKind {
+    Process,
+    Info,
+    Connections,
+    Cpu,
+    Disk,
+    Network,
+    Memory,
+}
+
+impl LayoutConfig {
+    /// 按名称加载内置预设，否则作为TOML布局文件读取
+    pub fn load(layout: &str) -> Result<Self, CliError> {
+        let config = match PRESETS.iter().find(|(name, _, _)| *name == layout) {
+            Some((_, _, preset)) => toml::from_str(preset)
+                .map_err(|e| CliError::FileSysError(format!("布局预设格式错误 {}: {}", layout, e)))?,
+            None => {
+                let path = Path::new(layout);
+                if !path.exists() {
+                    let presets = PRESETS
+                        .iter()
+                        .map(|(name, description, _)| format!("  {:<10}{}", name, description))
+                        .collect::<Vec<_>>()
+                        .join("\n");
+                    return Err(CliError::FileSysError(format!(
+                        "布局文件不存在 {}，可用的预设：\n{}",
+                        layout, presets
+                    )));
+                }
+                let content = std::fs::read_to_string(path)?;
+                toml::from_str(&content)
+                    .map_err(|e| CliError::FileSysError(format!("布局配置格式错误 {}: {}", path.display(), e)))?
+            }
+        };
+        Self::validate(&config)?;
+        Ok(config)
+    }
+
+    fn validate(&self) -> Result<(), CliError> {
+        let invalid = |message: String| Err(CliError::FileSysError(format!("布局配置无效：{}", message)));
+        if self.rows.is_empty() {
+            return invalid("至少需要一行".to_string());
+        }
+        let mut widgets = HashSet::new();
+        for (i, row) in self.rows.iter().enumerate() {
+            if row.columns.is_empty() {
+                return invalid(format!("第{}行没有面板", i + 1));
+            }
+            if row.ratio == 0 || row.columns.iter().any(|column| column.ratio == 0) {
+                return invalid(format!("第{}行的比例必须大于0", i + 1));
+            }
+            for column in &row.columns {
+                if !widgets.insert(column.widget) {
+                    return invalid(format!("面板{:?}重复出现", column.widget));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// 计算每个面板内容区域的左上角和右下角坐标，按先行后列的顺序排列
+    ///
+    /// 边框占用内容区域外的一格，`width`和`height`为最右、最下边框的位置
+    pub fn calculate(&self, width: u16, height: u16) -> Vec<(WidgetKind, Coordinate, Coordinate)> {
+        let row_bounds = split(height, self.rows.iter().map(|row| row.ratio));
+        let mut layout = vec![];
+        for (row, (top, bottom)) in self.rows.iter().zip(row_bounds) {
+            let column_bounds = split(width, row.columns.iter().map(|column| column.ratio));
+            for (column, (left, right)) in row.columns.iter().zip(column_bounds) {
+                layout.push((
+                    column.widget,
+                    Coordinate::new(left + 1, top + 1),
+                    Coordinate::new(right.saturating_sub(1), bottom.saturating_sub(1)),
+                ));
+            }
+        }
+        layout
+    }
+
+    /// 每个面板都不小于最小尺寸时需要的宽高
+    pub fn min_size(&self) -> (u16, u16) {
+        // 切分时向下取整，每段按比例至少分到最小尺寸加两侧边框
+        let need = |min: u16, ratio: u16, sum: u32| (u32::from(min) + 2) * sum / u32::from(ratio) + 1;
+        let row_sum = self.rows.iter().map(|row| u32::from(row.ratio)).sum();
+        let height = self
+            .rows
+            .iter()
+            .map(|row| need(MIN_PANEL_HEIGHT, row.ratio, row_sum))
+            .max()
+            .unwrap_or(0);
+        let width = self
+            .rows
+            .iter()
+            .flat_map(|row| {
+                let sum = row.columns.iter().map(|column| u32::from(column.ratio)).sum();
+                row.columns.iter().map(move |column| need(MIN_PANEL_WIDTH, column.ratio, sum))
+            })
+            .max()
+            .unwrap_or(0);
+        (width.min(u16::MAX as u32) as u16, height.min(u16::MAX as u32) as u16)
+    }
+}
+
+/// 将`0..=total`按比例切分，返回每段的起止边界
+fn split(total: u16, ratios: impl Iterator<Item = u16>) -> Vec<(u16, u16)> {
+    let ratios = ratios.map(u32::from).collect::<Vec<_>>();
+    let sum = ratios.iter().sum::<u32>().max(1);
+    let mut start = 0;
+    let mut acc = 0;
+    ratios
+        .iter()
+        .map(|ratio| {
+            acc += ratio;
+            let end = (u32::from(total) * acc / sum) as u16;
+            let bounds = (start, end);
+            start = end;
+            bounds
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_layout_presets() {
+        for (name, _, _) in PRESETS {
+            let config = LayoutConfig::load(name).unwrap();
+            // 最小尺寸下每个面板都不小于最小宽高
+            let (width, height) = config.min_size();
+            for (kind, left_top, right_bottom) in config.calculate(width, height) {
+                assert!(right_bottom.x + 1 - left_top.x >= MIN_PANEL_WIDTH, "{} {:?}", name, kind);
+                assert!(right_bottom.y + 1 - left_top.y >= MIN_PANEL_HEIGHT, "{} {:?}", name, kind);
+            }
+        }
+        let layout = LayoutConfig::load("default").unwrap().calculate(180, 39);
+        let (kind, left_top, right_bottom) = &layout[0];
+        assert_eq!(*kind, WidgetKind::Process);
+        assert_eq!((left_top.x, left_top.y, right_bottom.x, right_bottom.y), (1, 1, 179, 12));
+        // 中间一行按2:3:3切分
+        let (kind, left_top, right_bottom) = &layout[2];
+        assert_eq!(*kind, WidgetKind::Connections);
+        assert_eq!((left_top.x, left_top.y, right_bottom.x, right_bottom.y), (46, 14, 111, 25));
+    }
+
+    #[test]
+    fn test_invalid_layout() {
+        let config: LayoutConfig =
+            toml::from_str("[[rows]]\ncolumns = [{ widget = \"cpu\" }, { widget = \"cpu\", ratio = 2 }]").unwrap();
+        assert!(config.validate().is_err());
+        assert!(toml::from_str::<LayoutConfig>("[[rows]]\ncolumns = [{ widget = \"gpu\" }]").is_err());
+        // 拼错的字段名不会被静默忽略
+        assert!(toml::from_str::<LayoutConfig>("[[rows]]\nratoi = 2\ncolumns = [{ widget = \"cpu\" }]").is_err());
+        assert!(toml::from_str::<LayoutConfig>("[[rows]]\ncolumns = [{ widget = \"cpu\", raito = 2 }]").is_err());
+        assert!(matches!(config.validate(), Err(CliError::FileSysError(_))));
+        assert!(LayoutConfig::load("no-such-layout.toml").is_err());
+    }
+}
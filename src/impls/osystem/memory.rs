@@ -1,10 +1,12 @@
 use crate::ui::Coordinate;
+use crate::ui::chart::{self, History};
 use crate::ui::theme::Theme;
 use crate::ui::widget::Widget;
-use crate::utils::consts;
+use crate::utils::format::format_bytes;
 use crossterm::cursor::MoveTo;
 use crossterm::queue;
 use crossterm::style::{Print, SetForegroundColor};
+use std::collections::HashMap;
 use std::io::Stdout;
 use sysinfo::System;
 
@@ -17,23 +19,130 @@ pub struct MemoryWidget {
     used_memory: u64,
     free_memory: u64,
     available_memory: u64,
-    memory_usage: f64,
+    total_swap: u64,
+    used_swap: u64,
+    // 缓冲区、缓存、共享内存，仅Linux可用
+    breakdown: Option<(u64, u64, u64)>,
+    history: History, // 内存使用率历史
 }
+
+/// 一行显示内容：带标签的进度条或文本
+enum Line {
+    Gauge(&'static str, f64),
+    Text(String),
+}
+
 impl MemoryWidget {
     pub fn new(left_top: Coordinate, right_bottom: Coordinate, theme: Theme, sys: &mut System) -> Self {
-        Self {
+        let mut widget = Self {
             width: (right_bottom.x - left_top.x) + 1,
             height: (right_bottom.y - left_top.y) + 1,
             coordinate: left_top,
             theme,
-            total_memory: sys.total_memory(),
-            used_memory: sys.used_memory(),
-            free_memory: sys.free_memory(),
-            available_memory: sys.available_memory(),
-            memory_usage: (sys.used_memory() as f64 / sys.total_memory() as f64) * 100.0,
+            total_memory: 0,
+            used_memory: 0,
+            free_memory: 0,
+            available_memory: 0,
+            total_swap: 0,
+            used_swap: 0,
+            breakdown: None,
+            history: History::default(),
+        };
+        widget.update(sys);
+        widget
+    }
+
+    /// 刷新内存信息，系统信息需已刷新
+    pub fn update(&mut self, sys: &System) {
+        self.total_memory = sys.total_memory();
+        self.used_memory = sys.used_memory();
+        self.free_memory = sys.free_memory();
+        self.available_memory = sys.available_memory();
+        self.total_swap = sys.total_swap();
+        self.used_swap = sys.used_swap();
+        self.breakdown = read_breakdown();
+    }
+
+    /// 记录一次使用率历史，只在定时刷新时调用
+    pub fn sample(&mut self) {
+        self.history.push(self.memory_usage() * 100.0);
+    }
+
+    fn memory_usage(&self) -> f64 {
+        self.used_memory as f64 / self.total_memory.max(1) as f64
+    }
+
+    /// 按可用行数决定显示的内容，空间不足时优先显示进度条
+    fn lines(&self, rows: usize) -> Vec<Line> {
+        let memory = Line::Text(format!(
+            "已用 {} / 共 {}  可用 {}  空闲 {}",
+            format_bytes(self.used_memory),
+            format_bytes(self.total_memory),
+            format_bytes(self.available_memory),
+            format_bytes(self.free_memory)
+        ));
+        let breakdown = self.breakdown.map(|(buffers, cached, shared)| {
+            Line::Text(format!(
+                "缓冲 {}  缓存 {}  共享 {}",
+                format_bytes(buffers),
+                format_bytes(cached),
+                format_bytes(shared)
+            ))
+        });
+        let swap = if self.total_swap == 0 {
+            Line::Text("交换分区未启用".to_string())
+        } else {
+            Line::Text(format!(
+                "交换已用 {} / 共 {}",
+                format_bytes(self.used_swap),
+                format_bytes(self.total_swap)
+            ))
+        };
+        let memory_gauge = Line::Gauge("内存", self.memory_usage());
+        let swap_gauge = Line::Gauge("交换", self.used_swap as f64 / self.total_swap.max(1) as f64);
+        let full = [Some(memory_gauge), Some(memory), breakdown, Some(swap_gauge), Some(swap)];
+        let count = full.iter().flatten().count();
+        let mut lines = full.into_iter().flatten().collect::<Vec<Line>>();
+        if rows < count {
+            // 两个进度条放在最前面
+            let swap_gauge = lines.remove(lines.len() - 2);
+            lines.insert(1, swap_gauge);
+            lines.truncate(rows);
         }
+        lines
     }
 }
+
+/// 从`/proc/meminfo`读取缓冲区、缓存（含可回收的slab）和共享内存
+#[cfg(target_os = "linux")]
+fn read_breakdown() -> Option<(u64, u64, u64)> {
+    let content = std::fs::read_to_string("/proc/meminfo").ok()?;
+    let meminfo = parse_meminfo(&content);
+    Some((
+        *meminfo.get("Buffers")?,
+        meminfo.get("Cached")? + meminfo.get("SReclaimable").unwrap_or(&0),
+        *meminfo.get("Shmem")?,
+    ))
+}
+
+#[cfg(not(target_os = "linux"))]
+fn read_breakdown() -> Option<(u64, u64, u64)> {
+    None
+}
+
+/// 解析`/proc/meminfo`，单位转换为字节
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn parse_meminfo(content: &str) -> HashMap<&str, u64> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let (key, value) = line.split_once(':')?;
+            let kb = value.trim().trim_end_matches("kB").trim().parse::<u64>().ok()?;
+            Some((key.trim(), kb * 1024))
+        })
+        .collect()
+}
+
 impl Widget for MemoryWidget {
     fn coordinate(&self) -> Coordinate {
         self.coordinate.clone()
@@ -47,34 +156,79 @@ impl Widget for MemoryWidget {
         self.height
     }
 
+    fn set_area(&mut self, left_top: Coordinate, right_bottom: Coordinate) {
+        self.width = (right_bottom.x - left_top.x) + 1;
+        self.height = (right_bottom.y - left_top.y) + 1;
+        self.coordinate = left_top;
+    }
+
     fn render(&self, stdout: &mut Stdout) -> std::io::Result<()> {
-        queue!(stdout, SetForegroundColor(self.theme.primary_text_color()))?;
-        let (x, y) = (self.coordinate().x + 2, self.coordinate().y + 2);
+        // 标题占用第一行
+        let (x, mut y) = (self.coordinate.x + 1, self.coordinate.y + 1);
+        let width = self.width.saturating_sub(2) as usize;
+        let rows = self.height.saturating_sub(1) as usize;
+        let lines = self.lines(rows);
+        for line in &lines {
+            match line {
+                Line::Gauge(label, ratio) => {
+                    // 标签5列、百分比7列
+                    queue!(
+                        stdout,
+                        MoveTo(x, y),
+                        SetForegroundColor(self.theme.primary_text_color()),
+                        Print(format!("{} ", label)),
+                        SetForegroundColor(self.theme.usage_color(*ratio)),
+                        Print(chart::gauge(*ratio, width.saturating_sub(12))),
+                        SetForegroundColor(self.theme.primary_text_color()),
+                        Print(format!("{:>6.1}%", ratio * 100.0))
+                    )?;
+                }
+                Line::Text(text) => queue!(
+                    stdout,
+                    MoveTo(x, y),
+                    SetForegroundColor(self.theme.primary_text_color()),
+                    Print(chart::fit(text, width))
+                )?,
+            }
+            y += 1;
+        }
+
+        // 历史图至少需要3行（说明1行，图2行）
+        let graph_rows = rows - lines.len();
+        if graph_rows < 3 {
+            return Ok(());
+        }
         queue!(
             stdout,
             MoveTo(x, y),
-            Print(format!("内存总大小:  {:>10}MB", self.total_memory / consts::SIZE_MB))
-        )?;
-        queue!(
-            stdout,
-            MoveTo(x, y + 1),
-            Print(format!("已使用内存:  {:>10}MB", self.used_memory / consts::SIZE_MB))
-        )?;
-        queue!(
-            stdout,
-            MoveTo(x, y + 2),
-            Print(format!("空闲内存:    {:>10}MB", self.free_memory / consts::SIZE_MB))
-        )?;
-        queue!(
-            stdout,
-            MoveTo(x, y + 3),
-            Print(format!("可用内存:    {:>10}MB", self.available_memory / consts::SIZE_MB))
-        )?;
-        queue!(
-            stdout,
-            MoveTo(x, y + 4),
-            Print(format!("内存使用率:  {:>11.2}%", self.memory_usage))
+            Print(chart::fit(
+                &format!(
+                    "使用率历史 (近{}秒, 峰值{:.1}%)",
+                    self.history.span(width * 2).as_secs(),
+                    self.history.max()
+                ),
+                width
+            )),
+            SetForegroundColor(self.theme.secondary_color())
         )?;
+        let values = self.history.values();
+        for (i, line) in chart::braille_graph(&values, 100.0, width, graph_rows - 1).iter().enumerate() {
+            queue!(stdout, MoveTo(x, y + 1 + i as u16), Print(line))?;
+        }
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_meminfo() {
+        let meminfo =
+            parse_meminfo("MemTotal:       16314204 kB\nBuffers:          102400 kB\nHugePages_Total:       0\n");
+        assert_eq!(meminfo["MemTotal"], 16314204 * 1024);
+        assert_eq!(meminfo["Buffers"], 100 * 1024 * 1024);
+        assert_eq!(meminfo["HugePages_Total"], 0);
+    }
+}
@@ -1,4 +1,7 @@
+pub mod connection;
 pub mod cpu;
 pub mod disk;
+pub mod layout;
 pub mod memory;
+pub mod network;
 pub mod process;
@@ -0,0 +1,354 @@
+use crate::ui::Coordinate;
+use crate::ui::chart::{self, History};
+use crate::ui::theme::Theme;
+use crate::ui::widget::Widget;
+use crate::utils::format::format_bytes;
+use crossterm::cursor::MoveTo;
+use crossterm::event::KeyCode;
+use crossterm::queue;
+use crossterm::style::{Print, SetForegroundColor};
+use std::collections::HashMap;
+use std::io::Stdout;
+use std::time::Instant;
+use sysinfo::Networks;
+
+/// 每个网卡占用的行数
+const INTERFACE_ROWS: usize = 2;
+
+/// 每个网卡的接收、发送累计字节数
+type Totals = HashMap<String, (u64, u64)>;
+
+pub struct NetworkWidget {
+    coordinate: Coordinate,
+    width: u16,
+    height: u16,
+    theme: Theme,
+    networks: Networks,
+    interfaces: Vec<InterfaceInfo>,
+    offset: usize,                   // 滚动位置，第一个显示的网卡
+    last: Option<(Instant, Totals)>, // 上一次的收发累计值
+    rx_rate: f64,
+    tx_rate: f64,
+    rx_history: History,
+    tx_history: History,
+}
+struct InterfaceInfo {
+    name: String,
+    ips: Vec<String>, // IP地址，带前缀长度
+    rx_rate: f64,     // 接收速率，字节/秒
+    tx_rate: f64,     // 发送速率，字节/秒
+    total_rx: u64,    // 累计接收
+    total_tx: u64,    // 累计发送
+}
+
+impl NetworkWidget {
+    pub fn new(left_top: Coordinate, right_bottom: Coordinate, theme: Theme) -> Self {
+        let mut widget = Self {
+            width: (right_bottom.x - left_top.x) + 1,
+            height: (right_bottom.y - left_top.y) + 1,
+            coordinate: left_top,
+            theme,
+            networks: Networks::new(),
+            interfaces: vec![],
+            offset: 0,
+            last: None,
+            rx_rate: 0.0,
+            tx_rate: 0.0,
+            rx_history: History::default(),
+            tx_history: History::default(),
+        };
+        widget.update();
+        widget
+    }
+
+    /// 刷新网卡列表，根据两次收发累计值的差计算速率
+    pub fn update(&mut self) {
+        // 重新获取列表以发现新增或移除的网卡
+        self.networks.refresh_list();
+        let now = Instant::now();
+        let totals = self
+            .networks
+            .iter()
+            .map(|(name, data)| (name.clone(), (data.total_received(), data.total_transmitted())))
+            .collect::<Totals>();
+        let rates = self.last.as_ref().map(|(time, last)| {
+            let seconds = now.duration_since(*time).as_secs_f64().max(f64::EPSILON);
+            rates(&totals, last, seconds)
+        });
+        let mut interfaces = self
+            .networks
+            .iter()
+            .map(|(name, data)| {
+                let (total_rx, total_tx) = totals[name];
+                let (rx_rate, tx_rate) =
+                    rates.as_ref().and_then(|rates| rates.get(name).copied()).unwrap_or((0.0, 0.0));
+                InterfaceInfo {
+                    name: name.clone(),
+                    ips: data
+                        .ip_networks()
+                        .iter()
+                        .map(|ip| format!("{}/{}", ip.addr, ip.prefix))
+                        .collect(),
+                    rx_rate,
+                    tx_rate,
+                    total_rx,
+                    total_tx,
+                }
+            })
+            .collect::<Vec<_>>();
+        interfaces.sort_by(|a, b| a.name.cmp(&b.name));
+        if rates.is_some() {
+            self.rx_rate = interfaces.iter().map(|i| i.rx_rate).sum();
+            self.tx_rate = interfaces.iter().map(|i| i.tx_rate).sum();
+        }
+        self.interfaces = interfaces;
+        self.offset = self.offset.min(self.max_offset());
+        self.last = Some((now, totals));
+    }
+
+    /// 记录一次收发速率历史，只在定时刷新时调用
+    pub fn sample(&mut self) {
+        self.rx_history.push(self.rx_rate);
+        self.tx_history.push(self.tx_rate);
+    }
+
+    /// 除汇总行外的可用行数
+    fn content_rows(&self) -> usize {
+        self.height.saturating_sub(2) as usize
+    }
+
+    /// 可以同时显示的网卡个数，空间足够时至少留两行显示历史
+    fn visible_count(&self) -> usize {
+        let rows = self.content_rows();
+        let reserved = if rows >= INTERFACE_ROWS + 2 { 2 } else { 0 };
+        ((rows - reserved) / INTERFACE_ROWS).min(self.interfaces.len())
+    }
+
+    fn max_offset(&self) -> usize {
+        self.interfaces.len().saturating_sub(self.visible_count())
+    }
+
+    fn render_interface(
+        &self,
+        stdout: &mut Stdout,
+        x: u16,
+        y: u16,
+        width: usize,
+        interface: &InterfaceInfo,
+    ) -> std::io::Result<()> {
+        let ips = if interface.ips.is_empty() {
+            "无IP地址".to_string()
+        } else {
+            interface.ips.join(", ")
+        };
+        let traffic = format!(
+            "  ↓ {}/s  ↑ {}/s  累计 ↓ {} ↑ {}",
+            format_bytes(interface.rx_rate as u64),
+            format_bytes(interface.tx_rate as u64),
+            format_bytes(interface.total_rx),
+            format_bytes(interface.total_tx)
+        );
+        queue!(
+            stdout,
+            MoveTo(x, y),
+            SetForegroundColor(self.theme.highlight_color()),
+            Print(chart::fit(&interface.name, width)),
+            SetForegroundColor(self.theme.primary_text_color()),
+            Print(chart::fit(&format!("  {}", ips), width.saturating_sub(interface.name.len()))),
+            MoveTo(x, y + 1),
+            Print(chart::fit(&traffic, width))
+        )?;
+        Ok(())
+    }
+
+    /// 分别渲染接收和发送的历史，空间足够时用盲文图，否则用迷你折线图
+    fn render_history(&self, stdout: &mut Stdout, x: u16, y: u16, width: usize, rows: usize) -> std::io::Result<()> {
+        let histories = [("接收", &self.rx_history), ("发送", &self.tx_history)];
+        if rows < 4 {
+            for (i, (label, history)) in histories.iter().enumerate().take(rows) {
+                queue!(
+                    stdout,
+                    MoveTo(x, y + i as u16),
+                    SetForegroundColor(self.theme.primary_text_color()),
+                    Print(format!("{} ", label)),
+                    SetForegroundColor(self.theme.secondary_color()),
+                    Print(chart::sparkline(&history.values(), history.max(), width.saturating_sub(5)))
+                )?;
+            }
+            return Ok(());
+        }
+        // 每个图各占一半，说明1行
+        let graph_rows = rows / 2 - 1;
+        for (i, (label, history)) in histories.iter().enumerate() {
+            let y = y + (i * (graph_rows + 1)) as u16;
+            queue!(
+                stdout,
+                MoveTo(x, y),
+                SetForegroundColor(self.theme.primary_text_color()),
+                Print(chart::fit(
+                    &format!(
+                        "{}历史 (近{}秒, 峰值{}/s)",
+                        label,
+                        history.span(width * 2).as_secs(),
+                        format_bytes(history.max() as u64)
+                    ),
+                    width
+                )),
+                SetForegroundColor(self.theme.secondary_color())
+            )?;
+            for (row, line) in chart::braille_graph(&history.values(), history.max(), width, graph_rows)
+                .iter()
+                .enumerate()
+            {
+                queue!(stdout, MoveTo(x, y + 1 + row as u16), Print(line))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 根据两次收发累计值的差计算每个网卡的收发速率（字节/秒）
+///
+/// 网卡重置导致计数器变小时按0计算，上一次不存在的网卡速率为0
+fn rates(now: &Totals, last: &Totals, seconds: f64) -> HashMap<String, (f64, f64)> {
+    now.iter()
+        .map(|(name, (rx, tx))| {
+            let rate = last.get(name).map_or((0.0, 0.0), |(last_rx, last_tx)| {
+                (
+                    rx.saturating_sub(*last_rx) as f64 / seconds,
+                    tx.saturating_sub(*last_tx) as f64 / seconds,
+                )
+            });
+            (name.clone(), rate)
+        })
+        .collect()
+}
+
+impl Widget for NetworkWidget {
+    fn coordinate(&self) -> Coordinate {
+        self.coordinate.clone()
+    }
+
+    fn width(&self) -> u16 {
+        self.width
+    }
+
+    fn height(&self) -> u16 {
+        self.height
+    }
+
+    fn set_area(&mut self, left_top: Coordinate, right_bottom: Coordinate) {
+        self.width = (right_bottom.x - left_top.x) + 1;
+        self.height = (right_bottom.y - left_top.y) + 1;
+        self.coordinate = left_top;
+        self.offset = self.offset.min(self.max_offset());
+    }
+
+    fn render(&self, stdout: &mut Stdout) -> std::io::Result<()> {
+        // 标题占用第一行
+        let (x, mut y) = (self.coordinate.x + 1, self.coordinate.y + 1);
+        let width = self.width.saturating_sub(2) as usize;
+        if self.height < 2 {
+            return Ok(());
+        }
+        let visible = self.visible_count();
+        let mut summary = format!(
+            "接收 {}/s  发送 {}/s",
+            format_bytes(self.rx_rate as u64),
+            format_bytes(self.tx_rate as u64)
+        );
+        if self.interfaces.len() > visible {
+            summary += &format!(
+                "  网卡 {}-{}/{} (↑↓滚动)",
+                self.offset + 1,
+                self.offset + visible,
+                self.interfaces.len()
+            );
+        }
+        queue!(
+            stdout,
+            MoveTo(x, y),
+            SetForegroundColor(self.theme.primary_text_color()),
+            Print(chart::fit(&summary, width))
+        )?;
+        y += 1;
+
+        if self.interfaces.is_empty() && self.content_rows() > 0 {
+            queue!(stdout, MoveTo(x, y), Print("未发现网卡"))?;
+            y += 1;
+        }
+        for interface in self.interfaces.iter().skip(self.offset).take(visible) {
+            self.render_interface(stdout, x, y, width, interface)?;
+            y += INTERFACE_ROWS as u16;
+        }
+        let rows = (self.coordinate.y + self.height).saturating_sub(y) as usize;
+        self.render_history(stdout, x, y, width, rows)
+    }
+
+    fn handle_event(&mut self, event: KeyCode) -> bool {
+        // 滚动只需重新渲染，不刷新系统信息
+        match event {
+            KeyCode::Up => self.offset = self.offset.saturating_sub(1),
+            KeyCode::Down => self.offset = (self.offset + 1).min(self.max_offset()),
+            _ => {}
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn totals(items: &[(&str, u64, u64)]) -> Totals {
+        items.iter().map(|(name, rx, tx)| (name.to_string(), (*rx, *tx))).collect()
+    }
+
+    fn interface(name: &str) -> InterfaceInfo {
+        InterfaceInfo {
+            name: name.to_string(),
+            ips: vec![],
+            rx_rate: 0.0,
+            tx_rate: 0.0,
+            total_rx: 0,
+            total_tx: 0,
+        }
+    }
+
+    #[test]
+    fn test_rates() {
+        let last = totals(&[("eth0", 1000, 500), ("eth1", 100, 100), ("docker0", 10, 10)]);
+        // eth1计数器重置，wlan0新出现，docker0被移除
+        let now = totals(&[("eth0", 3000, 1500), ("eth1", 50, 20), ("wlan0", 800, 800)]);
+        let changed = rates(&now, &last, 2.0);
+        assert_eq!(changed.len(), 3);
+        assert_eq!(changed["eth0"], (1000.0, 500.0));
+        assert_eq!(changed["eth1"], (0.0, 0.0));
+        assert_eq!(changed["wlan0"], (0.0, 0.0));
+        assert!(rates(&Totals::new(), &last, 2.0).is_empty());
+        assert_eq!(rates(&now, &Totals::new(), 2.0)["eth0"], (0.0, 0.0));
+    }
+
+    #[test]
+    fn test_scroll_limits() {
+        // 高10行：汇总行2行，历史2行，可显示3个网卡
+        let mut widget = NetworkWidget::new(Coordinate::new(0, 0), Coordinate::new(39, 9), Theme::Ocean);
+        widget.interfaces = ["a", "b", "c", "d", "e"].into_iter().map(interface).collect();
+        assert_eq!((widget.visible_count(), widget.max_offset()), (3, 2));
+        for _ in 0..5 {
+            widget.handle_event(KeyCode::Down);
+        }
+        assert_eq!(widget.offset, 2);
+        widget.handle_event(KeyCode::Up);
+        assert_eq!(widget.offset, 1);
+        // 网卡不足一屏时不滚动
+        widget.interfaces.truncate(2);
+        assert_eq!((widget.visible_count(), widget.max_offset()), (2, 0));
+        widget.interfaces.clear();
+        assert_eq!((widget.visible_count(), widget.max_offset()), (0, 0));
+        // 空间不足时不显示历史，只显示网卡
+        widget.set_area(Coordinate::new(0, 0), Coordinate::new(39, 4));
+        widget.interfaces = ["a", "b", "c"].into_iter().map(interface).collect();
+        assert_eq!((widget.visible_count(), widget.max_offset()), (1, 2));
+    }
+}
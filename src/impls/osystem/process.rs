@@ -1,31 +1,90 @@
 use crate::ui::Coordinate;
+use crate::ui::chart;
 use crate::ui::theme::Theme;
 use crate::ui::widget::{List, Widget};
 use crate::utils::consts;
+use crate::utils::format::format_bytes;
+use chrono::{Local, TimeZone};
 use crossterm::cursor::MoveTo;
 use crossterm::event::KeyCode;
 use crossterm::queue;
 use crossterm::style::{Print, SetForegroundColor};
 use netstat2::{AddressFamilyFlags, ProtocolFlags, ProtocolSocketInfo, get_sockets_info};
-use std::collections::HashMap;
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
 use std::fmt::Display;
 use std::io::Stdout;
-use sysinfo::{Pid, System};
+use std::net::SocketAddr;
+use sysinfo::{Pid, Process, ProcessesToUpdate, Signal, System, Users};
+
+/// 表头：名称、排序快捷键、列起始位置，与`ProcessInfo`的显示格式对应
+const COLUMNS: [(&str, SortKey, u16); 6] = [
+    ("进程(n)", SortKey::Name, 0),
+    ("PID(p)", SortKey::Pid, 25),
+    ("用户(u)", SortKey::User, 35),
+    ("CPU(c)", SortKey::Cpu, 49),
+    ("内存(m)", SortKey::Mem, 60),
+    ("端口(o)", SortKey::Ports, 72),
+];
+
+/// 可发送的信号：信号、名称、说明
+const SIGNALS: [(Signal, &str, &str); 5] = [
+    (Signal::Term, "TERM", "请求进程正常退出"),
+    (Signal::Kill, "KILL", "强制结束进程，无法被捕获或忽略"),
+    (Signal::Stop, "STOP", "暂停进程"),
+    (Signal::Continue, "CONT", "继续运行已暂停的进程"),
+    (Signal::Hangup, "HUP", "挂起，常用于通知进程重新加载配置"),
+];
 
 pub struct ProcessWidget {
     coordinate: Coordinate,
     width: u16,
     height: u16,
     process_list: List<ProcessInfo>,
+    processes: Vec<ProcessInfo>, // 全部进程，未过滤
+    users: Users,
+    sort_key: SortKey,
+    descending: bool,
+    filter: String, // 按名称或命令行过滤，不区分大小写
+    editing: bool,  // 是否正在输入过滤条件
+    tree: bool,     // 是否按父子关系显示
+    collapsed: HashSet<u32>,
+    overlay: Option<Overlay>,
+    detail: Vec<String>,     // 详情面板的内容，每次刷新时重新读取
+    message: Option<String>, // 最近一次发送信号的结果，下次按键时清除
     theme: Theme,
 }
-#[derive(Debug)]
+
+/// 覆盖在进程列表上的弹出层，打开时接管所有按键
+enum Overlay {
+    /// 进程详情，`scroll`为滚动位置
+    Detail { pid: u32, scroll: usize },
+    /// 发送信号前的确认框，`selected`为选中的信号下标
+    Signal { pid: u32, name: String, selected: usize },
+}
+
+/// 排序的列
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SortKey {
+    Name,
+    Pid,
+    User,
+    Cpu,
+    Mem,
+    Ports,
+}
+
+#[derive(Debug, Clone)]
 struct ProcessInfo {
     name: String,
     pid: u32,
+    parent: Option<u32>,
+    prefix: String, // 树形视图中名称前的连接线
+    user: String,
+    cmd: String,
     cpu: f32,
     mem: u64,
-    ports: Vec<String>,
+    ports: Vec<(&'static str, u16)>,
 }
 impl Display for ProcessInfo {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -35,69 +94,637 @@ impl Display for ProcessInfo {
         // ^: 居中对齐
         write!(
             f,
-            "{:<25}{:<10}{:>8.2}%{:>10}MB  {:^10}",
-            self.name,
+            "{:<25}{:<10}{:<14}{:>8.2}%{:>10}MB  {}",
+            chart::fit(&format!("{}{}", self.prefix, self.name), 24),
             self.pid,
+            chart::fit(&self.user, 13),
             self.cpu,
             self.mem,
-            self.ports.join(" ")
+            self.ports
+                .iter()
+                .map(|(protocol, port)| format!("{}/{}", protocol, port))
+                .collect::<Vec<_>>()
+                .join(" ")
         )
     }
 }
 
+impl SortKey {
+    /// 数值列默认降序，文本列默认升序
+    fn default_descending(self) -> bool {
+        matches!(self, SortKey::Cpu | SortKey::Mem)
+    }
+
+    fn compare(self, a: &ProcessInfo, b: &ProcessInfo) -> Ordering {
+        match self {
+            SortKey::Name => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+            SortKey::Pid => a.pid.cmp(&b.pid),
+            SortKey::User => a.user.cmp(&b.user),
+            SortKey::Cpu => a.cpu.total_cmp(&b.cpu),
+            SortKey::Mem => a.mem.cmp(&b.mem),
+            // 按最小端口排序，没有端口的排在后面
+            SortKey::Ports => match (a.ports.first(), b.ports.first()) {
+                (Some(a), Some(b)) => a.1.cmp(&b.1),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => Ordering::Equal,
+            },
+        }
+    }
+}
+
+impl ProcessInfo {
+    fn matches(&self, filter: &str) -> bool {
+        filter.is_empty() || self.name.to_lowercase().contains(filter) || self.cmd.to_lowercase().contains(filter)
+    }
+}
+
+/// 生成树形列表时的中间数据，均按`ProcessWidget::processes`的下标索引
+struct Tree {
+    children: Vec<Vec<usize>>,
+    totals: Vec<ProcessInfo>, // CPU和内存为子树合计
+    visible: Vec<bool>,       // 子树中是否有匹配过滤条件的进程
+    rows: Vec<ProcessInfo>,
+}
+
+impl Tree {
+    /// 后序遍历累加子树的CPU和内存
+    fn aggregate(&mut self, idx: usize, filter: &str) {
+        for child in self.children[idx].clone() {
+            self.aggregate(child, filter);
+            let (cpu, mem) = (self.totals[child].cpu, self.totals[child].mem);
+            self.totals[idx].cpu += cpu;
+            self.totals[idx].mem += mem;
+            self.visible[idx] |= self.visible[child];
+        }
+        self.visible[idx] |= self.totals[idx].matches(filter);
+    }
+}
+
+/// 每个进程监听或连接的本地端口，去重后升序
+fn socket_map() -> HashMap<u32, Vec<(&'static str, u16)>> {
+    let sockets_info = get_sockets_info(
+        AddressFamilyFlags::IPV4 | AddressFamilyFlags::IPV6,
+        ProtocolFlags::TCP | ProtocolFlags::UDP,
+    )
+    .unwrap_or(vec![]);
+    let mut socket_map = HashMap::<u32, Vec<(&'static str, u16)>>::new();
+    sockets_info.iter().for_each(|socket_info| {
+        let protocol = match socket_info.protocol_socket_info {
+            ProtocolSocketInfo::Tcp(_) => "TCP",
+            ProtocolSocketInfo::Udp(_) => "UDP",
+        };
+        for pid in &socket_info.associated_pids {
+            socket_map.entry(*pid).or_default().push((protocol, socket_info.local_port()));
+        }
+    });
+    socket_map.values_mut().for_each(|ports| {
+        ports.sort_by_key(|(protocol, port)| (*port, *protocol));
+        ports.dedup();
+    });
+    socket_map
+}
+
+/// 进程打开的套接字，TCP显示本地、远端地址和状态
+fn process_sockets(pid: u32) -> Vec<String> {
+    get_sockets_info(
+        AddressFamilyFlags::IPV4 | AddressFamilyFlags::IPV6,
+        ProtocolFlags::TCP | ProtocolFlags::UDP,
+    )
+    .unwrap_or(vec![])
+    .into_iter()
+    .filter(|socket_info| socket_info.associated_pids.contains(&pid))
+    .map(|socket_info| match socket_info.protocol_socket_info {
+        ProtocolSocketInfo::Tcp(tcp) => format!(
+            "TCP {} -> {} {}",
+            SocketAddr::new(tcp.local_addr, tcp.local_port),
+            SocketAddr::new(tcp.remote_addr, tcp.remote_port),
+            tcp.state
+        ),
+        ProtocolSocketInfo::Udp(udp) => format!("UDP {}", SocketAddr::new(udp.local_addr, udp.local_port)),
+    })
+    .collect()
+}
+
+/// 向进程发送信号，返回给用户看的结果
+fn send_signal(pid: u32, signal: Signal, name: &str) -> String {
+    let pid = Pid::from_u32(pid);
+    let mut sys = System::new();
+    sys.refresh_processes(ProcessesToUpdate::Some(&[pid]));
+    match sys.process(pid).map(|process| process.kill_with(signal)) {
+        None => format!("进程{}已退出", pid),
+        Some(None) => format!("当前系统不支持信号{}", name),
+        Some(Some(true)) => format!("已向进程{}发送{}", pid, name),
+        Some(Some(false)) => format!("向进程{}发送{}失败，可能没有权限", pid, name),
+    }
+}
+
+/// 运行时长，如`2天3小时`、`5分12秒`
+fn format_duration(seconds: u64) -> String {
+    let (days, hours, minutes, seconds) = (seconds / 86400, seconds % 86400 / 3600, seconds % 3600 / 60, seconds % 60);
+    match (days, hours, minutes) {
+        (0, 0, 0) => format!("{}秒", seconds),
+        (0, 0, _) => format!("{}分{}秒", minutes, seconds),
+        (0, _, _) => format!("{}小时{}分", hours, minutes),
+        _ => format!("{}天{}小时", days, hours),
+    }
+}
+
 impl ProcessWidget {
     pub fn new(left_top: Coordinate, right_bottom: Coordinate, theme: Theme, sys: &System) -> Self {
         // 先计算基本字段
         let width = (right_bottom.x - left_top.x) + 1;
         let height = (right_bottom.y - left_top.y) + 1;
-        let sockets_info = get_sockets_info(
-            AddressFamilyFlags::IPV4 | AddressFamilyFlags::IPV6,
-            ProtocolFlags::TCP | ProtocolFlags::UDP,
-        )
-        .unwrap_or(vec![]);
-        let mut socket_map = HashMap::<u32, Vec<String>>::new();
-        sockets_info.iter().for_each(|socket_info| {
-            let protocol = match socket_info.protocol_socket_info {
-                ProtocolSocketInfo::Tcp(_) => "TCP",
-                ProtocolSocketInfo::Udp(_) => "UDP",
-            };
-            for pid in &socket_info.associated_pids {
-                socket_map.entry(pid.clone()).or_insert(vec![]).push(format!(
-                    "{}/{}",
-                    protocol,
-                    socket_info.local_port()
-                ));
-            }
-        });
-        Self {
+        let (list_left_top, list_right_bottom) = Self::list_area(&left_top, width, height);
+        let mut widget = Self {
             width,
             height,
             theme: theme.clone(),
-            coordinate: left_top.clone(),
-            process_list: {
-                let mut process_list = sys
-                    .processes()
+            coordinate: left_top,
+            process_list: List::new(list_left_top, list_right_bottom, theme),
+            processes: vec![],
+            users: Users::new(),
+            sort_key: SortKey::Cpu,
+            descending: true,
+            filter: String::new(),
+            editing: false,
+            tree: false,
+            collapsed: HashSet::new(),
+            overlay: None,
+            detail: vec![],
+            message: None,
+        };
+        widget.update(sys);
+        widget
+    }
+
+    /// 列表区域，表头占用第一行
+    fn list_area(left_top: &Coordinate, width: u16, height: u16) -> (Coordinate, Coordinate) {
+        let (x, y) = (left_top.x + 2, left_top.y + 2);
+        (Coordinate::new(x, y), Coordinate::new(x + width - 2, y + height - 3))
+    }
+
+    /// 重新读取进程列表，保留排序、过滤条件和选中的进程，系统信息需已刷新
+    pub fn update(&mut self, sys: &System) {
+        self.users.refresh_list();
+        let mut socket_map = socket_map();
+        let core_count = sys.physical_core_count().unwrap_or(1).max(1) as f32;
+        self.processes = sys
+            .processes()
+            .iter()
+            // Linux下的线程也会作为进程列出，只保留进程本身
+            .filter(|(_, process)| process.thread_kind().is_none())
+            .map(|(pid, process)| ProcessInfo {
+                name: process.name().to_string_lossy().to_string(),
+                pid: pid.as_u32(),
+                parent: process.parent().map(|pid| pid.as_u32()),
+                prefix: String::new(),
+                user: process
+                    .user_id()
+                    .and_then(|uid| self.users.get_user_by_id(uid))
+                    .map(|user| user.name().to_string())
+                    .unwrap_or(consts::UNKNOWN.to_string()),
+                cmd: process
+                    .cmd()
                     .iter()
-                    .map(|(pid, process)| ProcessInfo {
-                        name: process.name().to_str().unwrap().to_string(),
-                        pid: pid.as_u32(),
-                        cpu: process.cpu_usage() / sys.physical_core_count().unwrap() as f32,
-                        mem: process.memory() / consts::SIZE_MB,
-                        ports: socket_map.remove(&pid.as_u32()).unwrap_or(vec![]),
-                    })
-                    .collect::<Vec<ProcessInfo>>();
-                // 按照 CPU 使用率降序排序
-                process_list.sort_by(|a, b| b.cpu.partial_cmp(&a.cpu).unwrap());
-                let (x, y) = (left_top.x + 2, left_top.y + 2);
-                let mut list = List::new(
-                    Coordinate::new(x, y),
-                    Coordinate::new(x + width - 2, y + height - 3),
-                    theme.clone(),
-                );
-                list.set_items(process_list);
-                list
+                    .map(|arg| arg.to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join(" "),
+                cpu: process.cpu_usage() / core_count,
+                mem: process.memory() / consts::SIZE_MB,
+                ports: socket_map.remove(&pid.as_u32()).unwrap_or(vec![]),
+            })
+            .collect::<Vec<ProcessInfo>>();
+        if let Some(Overlay::Detail { pid, .. }) = self.overlay {
+            self.detail = match sys.process(Pid::from_u32(pid)) {
+                Some(process) => self.detail_lines(process, core_count),
+                None => vec![format!("进程{}已退出", pid)],
+            };
+        }
+        self.refresh_view();
+    }
+
+    /// 进程详情：命令行、工作目录、启动时间、资源占用、套接字和环境变量
+    fn detail_lines(&self, process: &Process, core_count: f32) -> Vec<String> {
+        let pid = process.pid().as_u32();
+        let user = self
+            .processes
+            .iter()
+            .find(|info| info.pid == pid)
+            .map(|info| info.user.as_str())
+            .unwrap_or(consts::UNKNOWN);
+        let path = |path: Option<&std::path::Path>| {
+            path.map(|path| path.display().to_string())
+                .unwrap_or(consts::UNKNOWN.to_string())
+        };
+        let start_time = Local
+            .timestamp_opt(process.start_time() as i64, 0)
+            .single()
+            .map(|time| time.format("%Y-%m-%d %H:%M:%S").to_string())
+            .unwrap_or(consts::UNKNOWN.to_string());
+        let disk = process.disk_usage();
+        let mut lines = vec![
+            format!(
+                "名称: {}  PID: {}  父进程: {}  用户: {}  状态: {}",
+                process.name().to_string_lossy(),
+                pid,
+                process
+                    .parent()
+                    .map(|pid| pid.to_string())
+                    .unwrap_or(consts::UNKNOWN.to_string()),
+                user,
+                process.status()
+            ),
+            format!(
+                "命令行: {}",
+                process
+                    .cmd()
+                    .iter()
+                    .map(|arg| arg.to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            ),
+            format!("可执行文件: {}", path(process.exe())),
+            format!("工作目录: {}", path(process.cwd())),
+            format!("启动时间: {}（已运行{}）", start_time, format_duration(process.run_time())),
+            format!(
+                "CPU: {:.2}%  内存: {}  虚拟内存: {}  线程数: {}",
+                process.cpu_usage() / core_count,
+                format_bytes(process.memory()),
+                format_bytes(process.virtual_memory()),
+                process
+                    .tasks()
+                    .map(|tasks| tasks.len().max(1).to_string())
+                    .unwrap_or(consts::UNKNOWN.to_string())
+            ),
+            format!(
+                "磁盘I/O: 累计读取 {} 写入 {}，最近一次刷新读取 {} 写入 {}",
+                format_bytes(disk.total_read_bytes),
+                format_bytes(disk.total_written_bytes),
+                format_bytes(disk.read_bytes),
+                format_bytes(disk.written_bytes)
+            ),
+        ];
+        let sockets = process_sockets(pid);
+        lines.push(format!("套接字 ({}):", sockets.len()));
+        lines.extend(sockets.into_iter().map(|socket| format!("  {}", socket)));
+        lines.push(format!("环境变量 ({}):", process.environ().len()));
+        lines.extend(process.environ().iter().map(|env| format!("  {}", env.to_string_lossy())));
+        lines
+    }
+
+    /// 按过滤条件和排序方式重新生成列表
+    fn refresh_view(&mut self) {
+        let selected = self.process_list.get_selected().map(|process| process.pid);
+        let filter = self.filter.to_lowercase();
+        let process_list = if self.tree {
+            self.tree_rows(&filter)
+        } else {
+            let mut process_list = self
+                .processes
+                .iter()
+                .filter(|process| process.matches(&filter))
+                .cloned()
+                .collect::<Vec<ProcessInfo>>();
+            process_list.sort_by(|a, b| self.compare(a, b));
+            process_list
+        };
+        let idx = selected
+            .and_then(|pid| process_list.iter().position(|process| process.pid == pid))
+            .unwrap_or(0);
+        self.process_list.set_items(process_list);
+        self.process_list.set_selected(idx);
+    }
+
+    fn compare(&self, a: &ProcessInfo, b: &ProcessInfo) -> Ordering {
+        let ordering = self.sort_key.compare(a, b).then(a.pid.cmp(&b.pid));
+        if self.descending { ordering.reverse() } else { ordering }
+    }
+
+    /// 按父子关系生成树形列表，CPU和内存为整个子树的合计，兄弟节点按当前排序方式排列
+    ///
+    /// 过滤时显示匹配的进程及其所有祖先，并忽略折叠状态
+    fn tree_rows(&self, filter: &str) -> Vec<ProcessInfo> {
+        let index = self
+            .processes
+            .iter()
+            .enumerate()
+            .map(|(idx, process)| (process.pid, idx))
+            .collect::<HashMap<_, _>>();
+        let parent_of = |idx: usize| self.processes[idx].parent.and_then(|pid| index.get(&pid)).copied();
+        let mut children = vec![vec![]; self.processes.len()];
+        let mut roots = vec![];
+        for idx in 0..self.processes.len() {
+            match parent_of(idx) {
+                Some(parent) if parent != idx => children[parent].push(idx),
+                _ => roots.push(idx),
+            }
+        }
+        // 父子关系成环时（如进程号被复用）环上的进程不在任何根节点之下，从环上断开一个作为根节点
+        let mut reached = vec![false; self.processes.len()];
+        let mut stack = roots.clone();
+        loop {
+            while let Some(idx) = stack.pop() {
+                if !reached[idx] {
+                    reached[idx] = true;
+                    stack.extend(&children[idx]);
+                }
+            }
+            let Some(mut idx) = reached.iter().position(|reached| !reached) else {
+                break;
+            };
+            // 未到达的进程都有父进程，沿父进程向上必然回到环上
+            let mut seen = HashSet::new();
+            while seen.insert(idx) {
+                idx = parent_of(idx).unwrap_or(idx);
+            }
+            if let Some(parent) = parent_of(idx) {
+                children[parent].retain(|child| *child != idx);
+            }
+            roots.push(idx);
+            stack.push(idx);
+        }
+        let mut tree = Tree {
+            children,
+            totals: self.processes.clone(),
+            visible: vec![false; self.processes.len()],
+            rows: vec![],
+        };
+        for &root in &roots {
+            tree.aggregate(root, filter);
+        }
+        roots.retain(|idx| tree.visible[*idx]);
+        roots.sort_by(|a, b| self.compare(&tree.totals[*a], &tree.totals[*b]));
+        for root in roots {
+            self.flatten(&mut tree, root, None, !filter.is_empty());
+        }
+        tree.rows
+    }
+
+    /// 深度优先展开子树，`parent`为父节点的连接线前缀和是否为最后一个子节点
+    fn flatten(&self, tree: &mut Tree, idx: usize, parent: Option<(&str, bool)>, expand: bool) {
+        let mut children = tree.children[idx]
+            .iter()
+            .copied()
+            .filter(|child| tree.visible[*child])
+            .collect::<Vec<_>>();
+        children.sort_by(|a, b| self.compare(&tree.totals[*a], &tree.totals[*b]));
+        let pid = tree.totals[idx].pid;
+        let expanded = expand || !self.collapsed.contains(&pid);
+        let marker = match (children.is_empty(), expanded) {
+            (true, _) if parent.is_none() => " ",
+            (true, _) => "─",
+            (false, true) => "▾",
+            (false, false) => "▸",
+        };
+        let (branch, indent) = match parent {
+            None => (String::new(), String::new()),
+            Some((prefix, true)) => (format!("{}└─", prefix), format!("{}  ", prefix)),
+            Some((prefix, false)) => (format!("{}├─", prefix), format!("{}│ ", prefix)),
+        };
+        let mut row = tree.totals[idx].clone();
+        row.prefix = format!("{}{} ", branch, marker);
+        tree.rows.push(row);
+        if !expanded {
+            return;
+        }
+        let count = children.len();
+        for (i, child) in children.into_iter().enumerate() {
+            self.flatten(tree, child, Some((&indent, i + 1 == count)), expand);
+        }
+    }
+
+    /// 按指定列排序，再次选择同一列时切换升降序
+    fn sort_by(&mut self, key: SortKey) {
+        if self.sort_key == key {
+            self.descending = !self.descending;
+        } else {
+            self.sort_key = key;
+            self.descending = key.default_descending();
+        }
+        self.refresh_view();
+    }
+
+    /// 选中指定进程，进程被过滤或折叠时清除过滤条件并展开其祖先
+    pub fn select_pid(&mut self, pid: u32) {
+        if !self.process_list.items().iter().any(|process| process.pid == pid) {
+            self.filter.clear();
+            let parents = self
+                .processes
+                .iter()
+                .map(|process| (process.pid, process.parent))
+                .collect::<HashMap<_, _>>();
+            // 限制层数，避免父子关系成环时死循环
+            let mut parent = parents.get(&pid).copied().flatten();
+            for _ in 0..parents.len() {
+                let Some(pid) = parent else { break };
+                self.collapsed.remove(&pid);
+                parent = parents.get(&pid).copied().flatten();
+            }
+            self.refresh_view();
+        }
+        match self.process_list.items().iter().position(|process| process.pid == pid) {
+            Some(idx) => self.process_list.set_selected(idx),
+            None => self.message = Some(format!("进程{}已退出", pid)),
+        }
+    }
+
+    /// 处理输入过滤条件时的按键，Enter确认，Esc清除
+    fn handle_filter_event(&mut self, event: KeyCode) {
+        match event {
+            KeyCode::Char(c) => self.filter.push(c),
+            KeyCode::Backspace => {
+                self.filter.pop();
+            }
+            KeyCode::Enter => self.editing = false,
+            KeyCode::Esc => {
+                self.filter.clear();
+                self.editing = false;
+            }
+            KeyCode::Up | KeyCode::Down => {
+                self.process_list.handle_event(event);
+                return;
+            }
+            _ => return,
+        }
+        self.refresh_view();
+    }
+
+    fn open_signal_dialog(&mut self, pid: u32) {
+        let name = self
+            .processes
+            .iter()
+            .find(|process| process.pid == pid)
+            .map(|process| process.name.clone())
+            .unwrap_or(consts::UNKNOWN.to_string());
+        self.overlay = Some(Overlay::Signal { pid, name, selected: 0 });
+    }
+
+    /// 处理弹出层的按键，返回true表示需要刷新系统信息
+    fn handle_overlay_event(&mut self, event: KeyCode) -> bool {
+        match &mut self.overlay {
+            Some(Overlay::Detail { pid, scroll }) => match event {
+                KeyCode::Up => *scroll = scroll.saturating_sub(1),
+                KeyCode::Down => *scroll = (*scroll + 1).min(self.detail.len().saturating_sub(1)),
+                KeyCode::Char('k') | KeyCode::Delete => {
+                    let pid = *pid;
+                    self.open_signal_dialog(pid);
+                }
+                KeyCode::Esc | KeyCode::Char('i') | KeyCode::Char('q') => self.overlay = None,
+                _ => {}
             },
+            Some(Overlay::Signal { pid, selected, .. }) => match event {
+                KeyCode::Left | KeyCode::Up | KeyCode::BackTab => {
+                    *selected = (*selected + SIGNALS.len() - 1) % SIGNALS.len();
+                }
+                KeyCode::Right | KeyCode::Down | KeyCode::Tab => *selected = (*selected + 1) % SIGNALS.len(),
+                KeyCode::Char(c @ '1'..='5') => *selected = c as usize - '1' as usize,
+                KeyCode::Enter => {
+                    let (signal, name, _) = SIGNALS[*selected];
+                    self.message = Some(send_signal(*pid, signal, name));
+                    self.overlay = None;
+                    return true;
+                }
+                KeyCode::Esc | KeyCode::Char('q') => self.overlay = None,
+                _ => {}
+            },
+            None => {}
+        }
+        false
+    }
+
+    /// 详情面板占用整个组件，内容按宽度折行
+    fn render_detail(&self, stdout: &mut Stdout, scroll: usize) -> std::io::Result<()> {
+        let (x, y) = (self.coordinate.x + 2, self.coordinate.y + 1);
+        let width = self.width.saturating_sub(3) as usize;
+        let rows = self.height.saturating_sub(3) as usize;
+        queue!(
+            stdout,
+            MoveTo(x, y),
+            SetForegroundColor(self.theme.highlight_color()),
+            Print(chart::fit("进程详情  ↑↓滚动  k发送信号  Esc返回", width)),
+            SetForegroundColor(self.theme.primary_text_color())
+        )?;
+        let lines = self.detail.iter().flat_map(|line| chart::wrap(line, width)).collect::<Vec<_>>();
+        let scroll = scroll.min(lines.len().saturating_sub(rows));
+        for (i, line) in lines.iter().skip(scroll).take(rows).enumerate() {
+            queue!(stdout, MoveTo(x, y + 1 + i as u16), Print(line))?;
+        }
+        Ok(())
+    }
+
+    /// 在进程列表中间绘制发送信号的确认框
+    fn render_signal_dialog(&self, stdout: &mut Stdout, pid: u32, name: &str, selected: usize) -> std::io::Result<()> {
+        let width = self.width.saturating_sub(4).min(56);
+        let height = 6;
+        let x = self.coordinate.x + (self.width - width) / 2;
+        let y = self.coordinate.y + 2 + self.height.saturating_sub(3 + height) / 2;
+        let inner = width.saturating_sub(4) as usize;
+        queue!(stdout, SetForegroundColor(self.theme.highlight_color()))?;
+        for row in 0..height {
+            let line = match row {
+                0 => format!("┌{}┐", "-".repeat(width as usize - 2)),
+                _ if row == height - 1 => format!("└{}┘", "-".repeat(width as usize - 2)),
+                _ => format!("|{}|", " ".repeat(width as usize - 2)),
+            };
+            queue!(stdout, MoveTo(x, y + row), Print(line))?;
+        }
+        let (_, _, description) = SIGNALS[selected];
+        queue!(
+            stdout,
+            MoveTo(x + 2, y + 1),
+            SetForegroundColor(self.theme.primary_text_color()),
+            Print(chart::fit(&format!("向进程 {} ({}) 发送信号", name, pid), inner)),
+            MoveTo(x + 2, y + 2)
+        )?;
+        for (i, (_, signal, _)) in SIGNALS.iter().enumerate() {
+            let color = if i == selected {
+                self.theme.highlight_color()
+            } else {
+                self.theme.secondary_color()
+            };
+            let option = if i == selected {
+                format!("[{}.{}]", i + 1, signal)
+            } else {
+                format!(" {}.{} ", i + 1, signal)
+            };
+            queue!(stdout, SetForegroundColor(color), Print(option))?;
+        }
+        queue!(
+            stdout,
+            MoveTo(x + 2, y + 3),
+            SetForegroundColor(self.theme.primary_text_color()),
+            Print(chart::fit(description, inner)),
+            MoveTo(x + 2, y + 4),
+            Print(chart::fit("Enter发送  Esc取消  ←→选择", inner))
+        )?;
+        Ok(())
+    }
+
+    fn render_header(&self, stdout: &mut Stdout) -> std::io::Result<()> {
+        let (x, y) = (self.coordinate.x + 2, self.coordinate.y + 1);
+        let width = self.width.saturating_sub(3);
+        for (title, key, offset) in COLUMNS {
+            if offset >= width {
+                break;
+            }
+            // 树形视图中CPU和内存为子树合计
+            let title = if self.tree && matches!(key, SortKey::Cpu | SortKey::Mem) {
+                title.replacen('(', "∑(", 1)
+            } else {
+                title.to_string()
+            };
+            let title = if key == self.sort_key {
+                queue!(stdout, SetForegroundColor(self.theme.highlight_color()))?;
+                format!("{}{}", title, if self.descending { "▼" } else { "▲" })
+            } else {
+                queue!(stdout, SetForegroundColor(self.theme.primary_text_color()))?;
+                title
+            };
+            queue!(
+                stdout,
+                MoveTo(x + offset, y),
+                Print(chart::fit(&title, (width - offset) as usize))
+            )?;
+        }
+
+        // 视图和过滤条件显示在表头右侧，空间不足时覆盖表头
+        let mut status = vec![];
+        if let Some(message) = &self.message {
+            status.push(message.clone());
+        }
+        if self.tree {
+            status.push("树形(t) ←折叠 →展开".to_string());
+        }
+        if self.editing || !self.filter.is_empty() {
+            status.push(format!(
+                "/{}{}  匹配 {}/{}",
+                self.filter,
+                if self.editing { "▏" } else { "" },
+                self.process_list.items().len(),
+                self.processes.len()
+            ));
+        }
+        if status.is_empty() {
+            return Ok(());
         }
+        let status = status.join("  ");
+        let header_width = COLUMNS[COLUMNS.len() - 1].2 + 10;
+        let (offset, available) = if width >= header_width + 20 {
+            (header_width, width - header_width)
+        } else {
+            (0, width)
+        };
+        if offset == 0 {
+            queue!(stdout, MoveTo(x, y), Print(" ".repeat(width as usize)))?;
+        }
+        queue!(
+            stdout,
+            MoveTo(x + offset, y),
+            SetForegroundColor(self.theme.highlight_color()),
+            Print(chart::fit(&status, available as usize))
+        )?;
+        Ok(())
     }
 }
 impl Widget for ProcessWidget {
@@ -114,29 +741,89 @@ impl Widget for ProcessWidget {
     }
 
     fn render(&self, stdout: &mut Stdout) -> std::io::Result<()> {
-        queue!(
-            stdout,
-            MoveTo(self.coordinate.x + 2, self.coordinate.y + 1),
-            SetForegroundColor(self.theme.primary_text_color())
-        )?;
-        queue!(
-            stdout,
-            Print(format!("{:<23}{:<10}{:>8}{:>10}{:>10}", "进程", "pid", "cpu", "内存", "端口"))
-        )?;
+        if let Some(Overlay::Detail { scroll, .. }) = &self.overlay {
+            return self.render_detail(stdout, *scroll);
+        }
+        self.render_header(stdout)?;
         self.process_list.render(stdout)?;
+        if let Some(Overlay::Signal { pid, name, selected }) = &self.overlay {
+            self.render_signal_dialog(stdout, *pid, name, *selected)?;
+        }
         Ok(())
     }
 
     fn handle_event(&mut self, event: KeyCode) -> bool {
+        self.message = None;
+        if self.overlay.is_some() {
+            return self.handle_overlay_event(event);
+        }
+        if self.editing {
+            self.handle_filter_event(event);
+            return false;
+        }
         match event {
-            KeyCode::Up | KeyCode::Down => self.process_list.handle_event(event),
+            // 移动选中项只需重新渲染，不刷新系统信息
+            KeyCode::Up | KeyCode::Down => {
+                self.process_list.handle_event(event);
+                false
+            }
+            KeyCode::Char('/') => {
+                self.editing = true;
+                false
+            }
+            KeyCode::Char('t') => {
+                self.tree = !self.tree;
+                self.refresh_view();
+                false
+            }
+            // 树形视图中折叠、展开选中进程的子进程
+            KeyCode::Left | KeyCode::Right if self.tree => {
+                if let Some(pid) = self.process_list.get_selected().map(|process| process.pid) {
+                    if event == KeyCode::Left {
+                        self.collapsed.insert(pid);
+                    } else {
+                        self.collapsed.remove(&pid);
+                    }
+                    self.refresh_view();
+                }
+                false
+            }
+            KeyCode::Char('n') => {
+                self.sort_by(SortKey::Name);
+                false
+            }
+            KeyCode::Char('p') => {
+                self.sort_by(SortKey::Pid);
+                false
+            }
+            KeyCode::Char('u') => {
+                self.sort_by(SortKey::User);
+                false
+            }
+            KeyCode::Char('c') => {
+                self.sort_by(SortKey::Cpu);
+                false
+            }
+            KeyCode::Char('m') => {
+                self.sort_by(SortKey::Mem);
+                false
+            }
+            KeyCode::Char('o') => {
+                self.sort_by(SortKey::Ports);
+                false
+            }
+            // 打开详情后刷新一次以读取详情
+            KeyCode::Char('i') => match self.process_list.get_selected().map(|process| process.pid) {
+                Some(pid) => {
+                    self.overlay = Some(Overlay::Detail { pid, scroll: 0 });
+                    self.detail = vec!["加载中...".to_string()];
+                    true
+                }
+                None => false,
+            },
             KeyCode::Char('k') | KeyCode::Delete => {
-                let process_info = self.process_list.get_selected();
-                let sys = System::new_all();
-                if process_info.is_some() {
-                    let pid = process_info.unwrap().pid;
-                    let process = sys.process(Pid::from_u32(pid)).unwrap();
-                    return process.kill();
+                if let Some(pid) = self.process_list.get_selected().map(|process| process.pid) {
+                    self.open_signal_dialog(pid);
                 }
                 false
             }
@@ -147,4 +834,126 @@ impl Widget for ProcessWidget {
     fn set_focus(&mut self, focused: bool) {
         self.process_list.set_focus(focused)
     }
+
+    fn set_area(&mut self, left_top: Coordinate, right_bottom: Coordinate) {
+        self.width = (right_bottom.x - left_top.x) + 1;
+        self.height = (right_bottom.y - left_top.y) + 1;
+        let (list_left_top, list_right_bottom) = Self::list_area(&left_top, self.width, self.height);
+        self.process_list.set_area(list_left_top, list_right_bottom);
+        self.coordinate = left_top;
+    }
+
+    fn is_editing(&self) -> bool {
+        self.editing || self.overlay.is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn process(pid: u32, parent: Option<u32>, name: &str, cpu: f32, mem: u64) -> ProcessInfo {
+        ProcessInfo {
+            name: name.to_string(),
+            pid,
+            parent,
+            prefix: String::new(),
+            user: "root".to_string(),
+            cmd: name.to_string(),
+            cpu,
+            mem,
+            ports: vec![],
+        }
+    }
+
+    /// 1 -> (2 -> 3, 4)，5的父进程是自己，6和7互为父进程
+    fn tree_widget() -> ProcessWidget {
+        let mut widget = ProcessWidget::new(
+            Coordinate::new(0, 0),
+            Coordinate::new(100, 30),
+            Theme::Cyberpunk,
+            &System::new(),
+        );
+        widget.processes = vec![
+            process(1, None, "init", 1.0, 10),
+            process(2, Some(1), "sshd", 2.0, 20),
+            process(3, Some(2), "nginx", 3.0, 30),
+            process(4, Some(1), "bash", 0.5, 5),
+            process(5, Some(5), "self", 0.1, 1),
+            process(6, Some(7), "a", 0.2, 2),
+            process(7, Some(6), "b", 0.3, 3),
+        ];
+        widget.tree = true;
+        widget
+    }
+
+    fn pids(rows: &[ProcessInfo]) -> Vec<u32> {
+        rows.iter().map(|row| row.pid).collect()
+    }
+
+    #[test]
+    fn test_tree_rows() {
+        let mut widget = tree_widget();
+        let rows = widget.tree_rows("");
+        // 按子树CPU合计降序，成环和父进程为自己的进程也作为根节点显示
+        assert_eq!(pids(&rows), vec![1, 2, 3, 4, 6, 7, 5]);
+        assert_eq!((rows[0].cpu, rows[0].mem), (6.5, 65));
+        assert_eq!((rows[1].cpu, rows[1].mem), (5.0, 50));
+        assert_eq!((rows[4].cpu, rows[4].mem), (0.5, 5));
+        assert_eq!(rows[0].prefix, "▾ ");
+        assert_eq!(rows[1].prefix, "├─▾ ");
+        assert_eq!(rows[2].prefix, "│ └── ");
+
+        // 过滤时保留匹配进程的祖先
+        assert_eq!(pids(&widget.tree_rows("nginx")), vec![1, 2, 3]);
+        assert_eq!(pids(&widget.tree_rows("self")), vec![5]);
+
+        // 折叠后隐藏子进程，过滤时忽略折叠状态
+        widget.collapsed.insert(2);
+        let rows = widget.tree_rows("");
+        assert_eq!(pids(&rows), vec![1, 2, 4, 6, 7, 5]);
+        assert_eq!(rows[1].prefix, "├─▸ ");
+        assert_eq!(pids(&widget.tree_rows("nginx")), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_sort_key() {
+        let mut a = process(10, None, "Zsh", 1.0, 100);
+        let mut b = process(2, None, "bash", 2.0, 100);
+        assert_eq!(SortKey::Name.compare(&a, &b), Ordering::Greater);
+        assert_eq!(SortKey::Pid.compare(&a, &b), Ordering::Greater);
+        assert_eq!(SortKey::Cpu.compare(&a, &b), Ordering::Less);
+        assert_eq!(SortKey::Mem.compare(&a, &b), Ordering::Equal);
+        // 没有端口的排在后面，都有端口时比较最小端口
+        a.ports = vec![("TCP", 8080)];
+        assert_eq!(SortKey::Ports.compare(&a, &b), Ordering::Less);
+        b.ports = vec![("TCP", 22), ("UDP", 9000)];
+        assert_eq!(SortKey::Ports.compare(&a, &b), Ordering::Greater);
+        assert!(SortKey::Cpu.default_descending());
+        assert!(!SortKey::Name.default_descending());
+
+        // 相同时按PID排序，降序时整体反转
+        let widget = tree_widget();
+        let c = process(3, None, "bash", 2.0, 100);
+        assert_eq!(widget.compare(&b, &c), Ordering::Greater);
+    }
+
+    #[test]
+    fn test_process_matches() {
+        let mut process = process(1, None, "Nginx", 0.0, 0);
+        process.cmd = "/usr/sbin/nginx -c /etc/NGINX/app.conf".to_string();
+        assert!(process.matches(""));
+        assert!(process.matches("nginx"));
+        assert!(process.matches("app.conf"));
+        assert!(!process.matches("apache"));
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(0), "0秒");
+        assert_eq!(format_duration(59), "59秒");
+        assert_eq!(format_duration(312), "5分12秒");
+        assert_eq!(format_duration(3600), "1小时0分");
+        assert_eq!(format_duration(2 * 86400 + 3 * 3600 + 59), "2天3小时");
+    }
 }
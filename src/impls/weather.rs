@@ -87,18 +87,23 @@ struct HeFengWeather<'a> {
     client: &'a Client,
 }
 impl<'a> HeFengWeather<'a> {
-    const HEFENG_GEO_API_URL: &'static str =
-        "https://mu4y3j6egv.re.qweatherapi.com/geo/v2/city/lookup?location={city}&key={apiKey}&gzip=n";
-    const HEFENG_API_URL: &'static str =
-        "https://mu4y3j6egv.re.qweatherapi.com/v7/weather/now?location={location}&key={apiKey}&gzip=n";
+    const HEFENG_API_HOST: &'static str = "https://mu4y3j6egv.re.qweatherapi.com";
+    // 替换API地址的环境变量，可指向`fun curl mock`启动的本地服务
+    const HEFENG_API_HOST_ENV: &'static str = "FUN_CLI_WEATHER_API";
+    const HEFENG_GEO_API_URL: &'static str = "{host}/geo/v2/city/lookup?location={city}&key={apiKey}&gzip=n";
+    const HEFENG_API_URL: &'static str = "{host}/v7/weather/now?location={location}&key={apiKey}&gzip=n";
     const HEFENG_API_KEY: &'static str = "1d7b188237fc43c5b83c12f1ed996da8";
     pub fn new(client: &'a Client) -> Self {
         Self { client }
     }
+    fn api_host() -> String {
+        std::env::var(Self::HEFENG_API_HOST_ENV).unwrap_or(Self::HEFENG_API_HOST.to_string())
+    }
     /// 获取实时天气信息
     pub fn get_now_weather(&self, city: &str) -> Result<WeatherResult, Box<dyn std::error::Error>> {
         let encoded_city = form_urlencoded::byte_serialize(city.as_bytes()).collect::<String>();
         let geo_api = Self::HEFENG_GEO_API_URL
+            .replace("{host}", &Self::api_host())
             .replace("{city}", &encoded_city)
             .replace("{apiKey}", Self::HEFENG_API_KEY);
         let resp = self.client.get(geo_api).send()?.error_for_status()?;
@@ -107,6 +112,7 @@ impl<'a> HeFengWeather<'a> {
         if let Some(locations) = res["location"].as_array() {
             if let Some(loc) = locations.first() {
                 let api_url = Self::HEFENG_API_URL
+                    .replace("{host}", &Self::api_host())
                     .replace("{location}", loc["id"].as_str().unwrap())
                     .replace("{apiKey}", Self::HEFENG_API_KEY);
                 let resp = self.client.get(api_url).send()?.error_for_status()?;
@@ -6,9 +6,10 @@ mod utils;
 
 use crate::cli::FunCli;
 use clap::Parser;
+use std::process::ExitCode;
 
-fn main() {
+fn main() -> ExitCode {
     let cli = FunCli::parse();
     let commands = cli.command;
-    commands.run();
+    commands.run()
 }
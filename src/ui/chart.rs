@@ -0,0 +1,171 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+use unicode_width::UnicodeWidthChar;
+
+/// 进度条中表示1/8格的字符
+const EIGHTHS: [char; 8] = [' ', '▏', '▎', '▍', '▌', '▋', '▊', '▉'];
+/// 迷你折线图的字符，从低到高
+const SPARKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+/// 盲文字符中每个点对应的位：[左列, 右列][从上到下第几行]
+const BRAILLE_DOTS: [[u32; 4]; 2] = [[0x01, 0x02, 0x04, 0x40], [0x08, 0x10, 0x20, 0x80]];
+
+/// 固定容量的历史数据，超出容量时丢弃最早的数据
+#[derive(Debug, Clone)]
+pub struct History {
+    samples: VecDeque<(Instant, f64)>,
+    capacity: usize,
+}
+
+/// 默认保留的数据个数，盲文图每个字符显示2个数据，足以铺满常见宽度的终端
+const DEFAULT_CAPACITY: usize = 512;
+
+impl Default for History {
+    fn default() -> Self {
+        Self::new(DEFAULT_CAPACITY)
+    }
+}
+
+impl History {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            samples: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    pub fn push(&mut self, value: f64) {
+        if self.samples.len() == self.capacity {
+            self.samples.pop_front();
+        }
+        self.samples.push_back((Instant::now(), value));
+    }
+
+    pub fn values(&self) -> Vec<f64> {
+        self.samples.iter().map(|(_, value)| *value).collect()
+    }
+
+    pub fn max(&self) -> f64 {
+        self.samples.iter().map(|(_, value)| *value).fold(0.0, f64::max)
+    }
+
+    /// 最近`count`个数据覆盖的时长
+    pub fn span(&self, count: usize) -> Duration {
+        let start = self.samples.len().saturating_sub(count.max(1));
+        match (self.samples.get(start), self.samples.back()) {
+            (Some((first, _)), Some((last, _))) => last.duration_since(*first),
+            _ => Duration::ZERO,
+        }
+    }
+}
+
+/// 进度条，末尾按1/8格显示不足一格的部分，如`█████▌    `
+pub fn gauge(ratio: f64, width: usize) -> String {
+    let eighths = (ratio.clamp(0.0, 1.0) * (width * 8) as f64).round() as usize;
+    let mut bar = "█".repeat(eighths / 8);
+    if !eighths.is_multiple_of(8) {
+        bar.push(EIGHTHS[eighths % 8]);
+    }
+    let len = bar.chars().count();
+    bar + &" ".repeat(width.saturating_sub(len))
+}
+
+/// 迷你折线图，每个字符表示一个数据，只显示最近的`width`个数据，不足时左侧留空
+pub fn sparkline(values: &[f64], max: f64, width: usize) -> String {
+    let values = &values[values.len().saturating_sub(width)..];
+    let line = values
+        .iter()
+        .map(|value| {
+            let level = (value / max.max(f64::EPSILON)).clamp(0.0, 1.0) * (SPARKS.len() - 1) as f64;
+            SPARKS[level.round() as usize]
+        })
+        .collect::<String>();
+    " ".repeat(width - values.len()) + &line
+}
+
+/// 盲文点阵面积图，每个字符包含2列×4行点，返回从上到下的每一行
+///
+/// 只显示最近的`width * 2`个数据，不足时左侧留空
+pub fn braille_graph(values: &[f64], max: f64, width: usize, height: usize) -> Vec<String> {
+    let dots = height * 4;
+    let values = &values[values.len().saturating_sub(width * 2)..];
+    let levels = values
+        .iter()
+        .map(|value| {
+            let level = ((value / max.max(f64::EPSILON)).clamp(0.0, 1.0) * dots as f64).round() as usize;
+            // 非零的数据至少显示一个点
+            if *value > 0.0 { level.max(1) } else { level }
+        })
+        .collect::<Vec<usize>>();
+    let offset = width * 2 - levels.len();
+    (0..height)
+        .map(|row| {
+            (0..width)
+                .map(|col| {
+                    let mut bits = 0;
+                    for (side, side_dots) in BRAILLE_DOTS.iter().enumerate() {
+                        let Some(level) = (col * 2 + side).checked_sub(offset).map(|idx| levels[idx]) else {
+                            continue;
+                        };
+                        for (dot, bit) in side_dots.iter().enumerate() {
+                            // 从底部数起的第几个点
+                            if (height - 1 - row) * 4 + (3 - dot) < level {
+                                bits |= bit;
+                            }
+                        }
+                    }
+                    char::from_u32(0x2800 + bits).unwrap_or(' ')
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// 按显示宽度截断，中文等宽字符占两列
+pub fn fit(text: &str, width: usize) -> String {
+    let mut used = 0;
+    text.chars()
+        .take_while(|c| {
+            used += c.width().unwrap_or(0);
+            used <= width
+        })
+        .collect()
+}
+
+/// 按显示宽度折行
+pub fn wrap(text: &str, width: usize) -> Vec<String> {
+    let mut lines = vec![];
+    let (mut line, mut used) = (String::new(), 0);
+    for c in text.chars() {
+        let char_width = c.width().unwrap_or(0);
+        if used + char_width > width && !line.is_empty() {
+            lines.push(std::mem::take(&mut line));
+            used = 0;
+        }
+        line.push(c);
+        used += char_width;
+    }
+    if !line.is_empty() || lines.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_charts() {
+        assert_eq!(gauge(0.5, 4), "██  ");
+        assert_eq!(gauge(0.25, 2), "▌ ");
+        assert_eq!(gauge(2.0, 3), "███");
+        assert_eq!(sparkline(&[0.0, 50.0, 100.0], 100.0, 5), "  ▁▅█");
+        // 数据不足时左侧留空，两个数据分别为满格和1/4格
+        assert_eq!(braille_graph(&[100.0, 25.0], 100.0, 2, 1), vec!["⠀⣇"]);
+        assert_eq!(braille_graph(&[100.0, 25.0], 100.0, 1, 2), vec!["⡇", "⣧"]);
+        assert_eq!(fit("中文abc", 5), "中文a");
+        assert_eq!(fit("中文", 3), "中");
+        assert_eq!(wrap("中文abc", 3), vec!["中", "文a", "bc"]);
+        assert_eq!(wrap("", 3), vec![""]);
+    }
+}
@@ -1,17 +1,33 @@
 use crate::error::CliError;
 use crossterm::event;
-use crossterm::event::{KeyCode, KeyEventKind};
+use crossterm::event::{KeyCode, KeyEventKind, KeyModifiers};
 use std::time::Duration;
 
+/// 输入事件：按键或终端尺寸变化
+pub enum InputEvent {
+    Key(KeyCode, KeyModifiers), // 原始模式下Ctrl+C作为按键送达，需结合修饰键判断
+    Resize(u16, u16),           // 新的终端宽高
+}
+
 /// 非阻塞输入轮询
 pub fn poll_input() -> Result<Option<KeyCode>, CliError> {
+    Ok(match poll_event()? {
+        Some(InputEvent::Key(code, _)) => Some(code),
+        _ => None,
+    })
+}
+
+/// 非阻塞轮询按键和终端尺寸变化
+pub fn poll_event() -> Result<Option<InputEvent>, CliError> {
     // 非阻塞输入轮询
     if event::poll(Duration::from_millis(100))? {
-        if let event::Event::Key(key_event) = event::read()? {
+        match event::read()? {
             // 只处理按下事件
-            if key_event.kind == KeyEventKind::Press {
-                return Ok(Some(key_event.code));
+            event::Event::Key(key_event) if key_event.kind == KeyEventKind::Press => {
+                return Ok(Some(InputEvent::Key(key_event.code, key_event.modifiers)));
             }
+            event::Event::Resize(width, height) => return Ok(Some(InputEvent::Resize(width, height))),
+            _ => {}
         }
     }
     Ok(None)
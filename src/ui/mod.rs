@@ -1,3 +1,4 @@
+pub mod chart;
 pub mod event;
 pub mod theme;
 pub mod widget;
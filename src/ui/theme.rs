@@ -50,4 +50,14 @@ impl Theme {
             Theme::Aurora => Color::Rgb { r: 255, g: 105, b: 180 },
         }
     }
+    /// 按使用率取色：低于60%为辅助色，低于85%为文本色，否则为高亮色
+    pub fn usage_color(&self, ratio: f64) -> Color {
+        if ratio < 0.6 {
+            self.secondary_color()
+        } else if ratio < 0.85 {
+            self.primary_text_color()
+        } else {
+            self.highlight_color()
+        }
+    }
 }
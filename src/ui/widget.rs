@@ -1,4 +1,5 @@
 use crate::ui::Coordinate;
+use crate::ui::chart::fit;
 use crate::ui::theme::Theme;
 use crossterm::cursor::MoveTo;
 use crossterm::event::KeyCode;
@@ -6,6 +7,7 @@ use crossterm::queue;
 use crossterm::style::{Print, ResetColor, SetForegroundColor};
 use std::fmt::Display;
 use std::io::{self, Stdout, Write};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
 // 所有UI组件的基础特征
 pub trait Widget {
@@ -32,6 +34,17 @@ pub trait Widget {
     fn set_focus(&mut self, focused: bool) {
         // 默认空实现，需要焦点的组件可重写
     }
+
+    /// 是否正在输入文本，输入时所有按键都交给组件处理
+    fn is_editing(&self) -> bool {
+        false
+    }
+
+    /// 移动到新的区域并保留组件状态，坐标含义与组件的构造函数一致
+    fn set_area(&mut self, _left_top: Coordinate, _right_bottom: Coordinate) {
+        // 默认空实现，位置固定的组件无需重写
+    }
+
     /// 在组件窗口范围内渲染，超出范围的内容被忽略
     fn render_on_window(&self, stdout: &mut Stdout, coordinate: Coordinate, content: String) -> io::Result<()> {
         queue!(stdout, MoveTo(coordinate.x, coordinate.y), Print(content))?;
@@ -109,9 +122,14 @@ impl<T: Widget> Panel<T> {
         )?;
         Ok(())
     }
-    /// 更新子组件
-    pub fn update_widget(&mut self, new_widget: T) {
-        self.child = new_widget;
+    pub fn set_title(&mut self, title: &str) {
+        self.title = title.to_string();
+    }
+    pub fn child(&self) -> &T {
+        &self.child
+    }
+    pub fn child_mut(&mut self) -> &mut T {
+        &mut self.child
     }
 }
 
@@ -146,6 +164,15 @@ impl<T: Widget> Widget for Panel<T> {
         // 将焦点状态传递给子组件
         self.child.set_focus(focused);
     }
+
+    fn is_editing(&self) -> bool {
+        self.child.is_editing()
+    }
+
+    fn set_area(&mut self, left_top: Coordinate, right_bottom: Coordinate) {
+        // 面板的位置由子组件决定
+        self.child.set_area(left_top, right_bottom);
+    }
 }
 
 // 列表组件（支持选中和滚动）
@@ -189,6 +216,14 @@ impl<T: Display> List<T> {
     pub fn get_selected(&self) -> Option<&T> {
         self.items.get(self.selected_idx)
     }
+    pub fn items(&self) -> &[T] {
+        &self.items
+    }
+    /// 选中指定项，超出范围时选中最后一项
+    pub fn set_selected(&mut self, idx: usize) {
+        self.selected_idx = idx.min(self.items.len().saturating_sub(1));
+        self.adjust_scroll();
+    }
 
     /// 确保选中项在可视区域内（自动滚动）
     fn adjust_scroll(&mut self) {
@@ -233,13 +268,7 @@ impl<T: Display> Widget for List<T> {
 
             // 截断过长的文本以适应宽度
             let max_len = (self.width - 2) as usize;
-            let item_str = item.to_string();
-            let display_str = if item_str.len() > max_len {
-                format!("{}...", &item_str[..max_len - 3])
-            } else {
-                item_str
-            };
-            queue!(stdout, Print(display_str), ResetColor)?;
+            queue!(stdout, Print(truncate(item.to_string(), max_len)), ResetColor)?;
         }
         // stdout.flush()?;
         Ok(())
@@ -272,6 +301,249 @@ impl<T: Display> Widget for List<T> {
     fn set_focus(&mut self, focused: bool) {
         self.focused = focused;
     }
+
+    fn set_area(&mut self, left_top: Coordinate, right_bottom: Coordinate) {
+        self.width = right_bottom.x - left_top.x;
+        self.height = right_bottom.y - left_top.y;
+        self.coordinate = left_top;
+        // 可视行数变化后保持选中项可见
+        self.adjust_scroll();
+    }
+}
+
+/// 按显示宽度截断过长的文本并以省略号结尾，避免截断在多字节字符中间或中文超出面板
+fn truncate(text: String, max_len: usize) -> String {
+    if text.width() > max_len {
+        format!("{}...", fit(&text, max_len.saturating_sub(3)))
+    } else {
+        text
+    }
+}
+
+/// 跳过开头至少`columns`个显示列，不拆开宽字符，返回实际跳过的列数和剩余的文本
+fn skip_columns(text: &str, columns: usize) -> (usize, &str) {
+    let mut skipped = 0;
+    for (idx, c) in text.char_indices() {
+        if skipped >= columns {
+            return (skipped, &text[idx..]);
+        }
+        skipped += c.width().unwrap_or(0);
+    }
+    (skipped, "")
+}
+
+// 文本编辑组件（支持单行和多行）
+pub struct TextArea {
+    coordinate: Coordinate,
+    width: u16,
+    height: u16,
+    lines: Vec<String>,   // 文本内容，按行存储
+    row: usize,           // 光标所在行
+    col: usize,           // 光标所在列（字符索引）
+    scroll_offset: usize, // 垂直滚动偏移量
+    multiline: bool,      // 是否允许换行
+    focused: bool,
+    theme: Theme,
+}
+
+impl TextArea {
+    pub fn new(left_top: Coordinate, right_bottom: Coordinate, theme: Theme, multiline: bool) -> Self {
+        Self {
+            width: right_bottom.x - left_top.x,
+            height: right_bottom.y - left_top.y,
+            coordinate: left_top,
+            lines: vec![String::new()],
+            row: 0,
+            col: 0,
+            scroll_offset: 0,
+            multiline,
+            focused: false,
+            theme,
+        }
+    }
+
+    /// 替换全部文本，光标移动到末尾
+    pub fn set_text(&mut self, text: &str) {
+        self.lines = if self.multiline {
+            text.split('\n').map(str::to_string).collect()
+        } else {
+            vec![text.replace('\n', " ")]
+        };
+        self.row = self.lines.len() - 1;
+        self.col = self.line_len();
+        self.scroll_offset = 0;
+        self.adjust_scroll();
+    }
+
+    pub fn text(&self) -> String {
+        self.lines.join("\n")
+    }
+
+    // 标题占用第一行，内容从第二行开始
+    fn visible_lines(&self) -> usize {
+        self.height.saturating_sub(1).max(1) as usize
+    }
+
+    fn visible_width(&self) -> usize {
+        self.width.saturating_sub(2).max(1) as usize
+    }
+
+    fn line_len(&self) -> usize {
+        self.lines[self.row].chars().count()
+    }
+
+    /// 光标所在位置的字节索引
+    fn byte_idx(&self) -> usize {
+        let line = &self.lines[self.row];
+        line.char_indices().nth(self.col).map(|(idx, _)| idx).unwrap_or(line.len())
+    }
+
+    /// 光标前的内容占用的显示列数，中文等宽字符占两列
+    fn cursor_column(&self) -> usize {
+        self.lines[self.row]
+            .chars()
+            .take(self.col)
+            .map(|c| c.width().unwrap_or(0))
+            .sum()
+    }
+
+    /// 光标所在行过长时水平滚动，保证光标可见，返回跳过的显示列数
+    fn col_offset(&self) -> usize {
+        let columns = self.cursor_column().saturating_sub(self.visible_width() - 1);
+        skip_columns(&self.lines[self.row], columns).0
+    }
+
+    fn adjust_scroll(&mut self) {
+        let visible_lines = self.visible_lines();
+        if self.row < self.scroll_offset {
+            self.scroll_offset = self.row;
+        } else if self.row >= self.scroll_offset + visible_lines {
+            self.scroll_offset = self.row + 1 - visible_lines;
+        }
+    }
+
+    /// 光标在终端中的位置
+    pub fn cursor_position(&self) -> Coordinate {
+        Coordinate::new(
+            self.coordinate.x + 1 + (self.cursor_column() - self.col_offset()) as u16,
+            self.coordinate.y + 1 + (self.row - self.scroll_offset) as u16,
+        )
+    }
+}
+
+impl Widget for TextArea {
+    fn coordinate(&self) -> Coordinate {
+        self.coordinate.clone()
+    }
+    fn width(&self) -> u16 {
+        self.width
+    }
+    fn height(&self) -> u16 {
+        self.height
+    }
+
+    fn render(&self, stdout: &mut Stdout) -> io::Result<()> {
+        let color = if self.focused {
+            self.theme.highlight_color()
+        } else {
+            self.theme.primary_text_color()
+        };
+        queue!(stdout, SetForegroundColor(color))?;
+        for (i, line) in self
+            .lines
+            .iter()
+            .skip(self.scroll_offset)
+            .take(self.visible_lines())
+            .enumerate()
+        {
+            // 只有光标所在行水平滚动
+            let offset = if self.scroll_offset + i == self.row {
+                self.col_offset()
+            } else {
+                0
+            };
+            let content = fit(skip_columns(line, offset).1, self.visible_width());
+            queue!(
+                stdout,
+                MoveTo(self.coordinate.x + 1, self.coordinate.y + 1 + i as u16),
+                Print(content)
+            )?;
+        }
+        queue!(stdout, ResetColor)?;
+        Ok(())
+    }
+
+    fn handle_event(&mut self, event: KeyCode) -> bool {
+        match event {
+            KeyCode::Char(c) => {
+                let idx = self.byte_idx();
+                self.lines[self.row].insert(idx, c);
+                self.col += 1;
+            }
+            KeyCode::Enter if self.multiline => {
+                let idx = self.byte_idx();
+                let rest = self.lines[self.row].split_off(idx);
+                self.lines.insert(self.row + 1, rest);
+                self.row += 1;
+                self.col = 0;
+            }
+            KeyCode::Backspace => {
+                if self.col > 0 {
+                    self.col -= 1;
+                    let idx = self.byte_idx();
+                    self.lines[self.row].remove(idx);
+                } else if self.row > 0 {
+                    // 行首退格：与上一行合并
+                    let line = self.lines.remove(self.row);
+                    self.row -= 1;
+                    self.col = self.line_len();
+                    self.lines[self.row].push_str(&line);
+                }
+            }
+            KeyCode::Delete => {
+                if self.col < self.line_len() {
+                    let idx = self.byte_idx();
+                    self.lines[self.row].remove(idx);
+                } else if self.row + 1 < self.lines.len() {
+                    let line = self.lines.remove(self.row + 1);
+                    self.lines[self.row].push_str(&line);
+                }
+            }
+            KeyCode::Left => {
+                if self.col > 0 {
+                    self.col -= 1;
+                } else if self.row > 0 {
+                    self.row -= 1;
+                    self.col = self.line_len();
+                }
+            }
+            KeyCode::Right => {
+                if self.col < self.line_len() {
+                    self.col += 1;
+                } else if self.row + 1 < self.lines.len() {
+                    self.row += 1;
+                    self.col = 0;
+                }
+            }
+            KeyCode::Up if self.multiline && self.row > 0 => {
+                self.row -= 1;
+                self.col = self.col.min(self.line_len());
+            }
+            KeyCode::Down if self.multiline && self.row + 1 < self.lines.len() => {
+                self.row += 1;
+                self.col = self.col.min(self.line_len());
+            }
+            KeyCode::Home => self.col = 0,
+            KeyCode::End => self.col = self.line_len(),
+            _ => return false,
+        }
+        self.adjust_scroll();
+        true
+    }
+
+    fn set_focus(&mut self, focused: bool) {
+        self.focused = focused;
+    }
 }
 
 #[cfg(test)]
@@ -339,4 +611,49 @@ mod tests {
         stdout.flush().unwrap();
         sleep(Duration::from_secs(1000));
     }
+
+    #[test]
+    fn test_truncate() {
+        assert_eq!(truncate("abc".to_string(), 3), "abc");
+        assert_eq!(truncate("abcdef".to_string(), 5), "ab...");
+        // 中文占两列
+        assert_eq!(truncate("中文".to_string(), 4), "中文");
+        assert_eq!(truncate("中文名称".to_string(), 7), "中文...");
+    }
+
+    #[test]
+    fn test_text_area_edit() {
+        let mut area = TextArea::new(Coordinate::new(0, 0), Coordinate::new(20, 5), Theme::Ocean, true);
+        for c in "ab中".chars() {
+            area.handle_event(KeyCode::Char(c));
+        }
+        area.handle_event(KeyCode::Left);
+        area.handle_event(KeyCode::Enter);
+        area.handle_event(KeyCode::Char('x'));
+        assert_eq!(area.text(), "ab\nx中");
+        area.handle_event(KeyCode::Home);
+        area.handle_event(KeyCode::Backspace);
+        area.handle_event(KeyCode::Delete);
+        assert_eq!(area.text(), "ab中");
+
+        let mut single = TextArea::new(Coordinate::new(0, 0), Coordinate::new(20, 2), Theme::Ocean, false);
+        single.set_text("GET\nPOST");
+        assert!(!single.handle_event(KeyCode::Enter));
+        assert_eq!(single.text(), "GET POST");
+    }
+
+    #[test]
+    fn test_text_area_cursor_width() {
+        // 内容区宽8列
+        let mut area = TextArea::new(Coordinate::new(0, 0), Coordinate::new(10, 2), Theme::Ocean, false);
+        area.set_text("中文abc");
+        assert_eq!((area.cursor_position().x, area.col_offset()), (8, 0));
+        // 超出宽度时整字滚动，光标仍在可视区域内
+        area.handle_event(KeyCode::Char('d'));
+        assert_eq!((area.cursor_position().x, area.col_offset()), (7, 2));
+        area.handle_event(KeyCode::Home);
+        assert_eq!((area.cursor_position().x, area.col_offset()), (1, 0));
+        assert_eq!(skip_columns("中文a", 1), (2, "文a"));
+        assert_eq!(skip_columns("中文a", 5), (5, ""));
+    }
 }
@@ -0,0 +1,58 @@
+use crate::utils::consts::{SIZE_GB, SIZE_KB, SIZE_MB};
+
+/// 将字节数格式化为便于阅读的大小，如`1.50MB`
+pub fn format_bytes(bytes: u64) -> String {
+    if bytes >= SIZE_GB {
+        format!("{:.2}GB", bytes as f64 / SIZE_GB as f64)
+    } else if bytes >= SIZE_MB {
+        format!("{:.2}MB", bytes as f64 / SIZE_MB as f64)
+    } else if bytes >= SIZE_KB {
+        format!("{:.2}KB", bytes as f64 / SIZE_KB as f64)
+    } else {
+        format!("{}B", bytes)
+    }
+}
+
+/// 按`hexdump -C`的格式输出，每行16字节：偏移量、十六进制、可打印字符
+pub fn hex_dump(bytes: &[u8]) -> Vec<String> {
+    bytes
+        .chunks(16)
+        .enumerate()
+        .map(|(i, chunk)| {
+            let hex = (0..16)
+                .map(|j| match chunk.get(j) {
+                    Some(byte) => format!("{:02x}", byte),
+                    None => "  ".to_string(),
+                })
+                .collect::<Vec<String>>();
+            let ascii = chunk
+                .iter()
+                .map(|&byte| {
+                    if byte.is_ascii_graphic() || byte == b' ' {
+                        byte as char
+                    } else {
+                        '.'
+                    }
+                })
+                .collect::<String>();
+            format!("{:08x}  {}  {}  |{}|", i * 16, hex[..8].join(" "), hex[8..].join(" "), ascii)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hex_dump() {
+        let lines = hex_dump(b"\x89PNG\r\n\x1a\n\x00\x00\x00\x0dIHDR+");
+        assert_eq!(
+            lines,
+            vec![
+                "00000000  89 50 4e 47 0d 0a 1a 0a  00 00 00 0d 49 48 44 52  |.PNG........IHDR|",
+                "00000010  2b                                                |+|",
+            ]
+        );
+    }
+}
@@ -1 +1,3 @@
 pub mod consts;
+pub mod format;
+pub mod storage;
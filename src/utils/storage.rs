@@ -0,0 +1,49 @@
+use crate::error::CliError;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// 可覆盖数据目录的环境变量
+const HOME_ENV: &str = "FUN_CLI_HOME";
+
+/// 本地数据目录，默认为`~/.fun-cli`，不存在时自动创建
+///
+/// `sub_dir`为空时返回数据目录本身
+pub fn data_dir(sub_dir: &str) -> Result<PathBuf, CliError> {
+    let home = match std::env::var_os(HOME_ENV) {
+        Some(dir) => PathBuf::from(dir),
+        None => dirs::home_dir()
+            .ok_or_else(|| CliError::FileSysError("无法获取用户目录".to_string()))?
+            .join(".fun-cli"),
+    };
+    let dir = if sub_dir.is_empty() { home } else { home.join(sub_dir) };
+    std::fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
+
+/// 写入只有当前用户可以读写的文件（Unix下权限为0600），用于会话、Cookie等包含凭据的数据
+pub fn write_private(path: &Path, content: impl AsRef<[u8]>) -> Result<(), CliError> {
+    let mut options = OpenOptions::new();
+    options.write(true).truncate(true);
+    open_private(path, &mut options)?.write_all(content.as_ref())?;
+    Ok(())
+}
+
+/// 追加到只有当前用户可以读写的文件，用于请求历史等包含凭据的日志
+pub fn append_private(path: &Path, content: impl AsRef<[u8]>) -> Result<(), CliError> {
+    let mut options = OpenOptions::new();
+    options.append(true);
+    open_private(path, &mut options)?.write_all(content.as_ref())?;
+    Ok(())
+}
+
+fn open_private(path: &Path, options: &mut OpenOptions) -> Result<File, CliError> {
+    options.create(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(options, 0o600);
+    let file = options.open(path)?;
+    // 已存在的文件不受mode影响，写入前收紧权限
+    #[cfg(unix)]
+    file.set_permissions(std::os::unix::fs::PermissionsExt::from_mode(0o600))?;
+    Ok(file)
+}